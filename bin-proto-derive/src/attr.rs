@@ -1,4 +1,5 @@
 use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
 use std::fmt;
 use syn::{parenthesized, punctuated::Punctuated, Error, Result, Token};
 
@@ -7,6 +8,7 @@ use syn::{parenthesized, punctuated::Punctuated, Error, Result, Token};
 pub struct Attrs {
     pub bits: Option<syn::Expr>,
     pub ctx: Option<Ctx>,
+    pub field_ctx: Option<syn::Expr>,
     pub ctx_generics: Option<Vec<syn::GenericParam>>,
     pub skip_encode: bool,
     pub skip_decode: bool,
@@ -16,10 +18,30 @@ pub struct Attrs {
     pub magic: Option<syn::Expr>,
     pub pad_after: Option<syn::Expr>,
     pub pad_before: Option<syn::Expr>,
+    pub pad_value: Option<syn::Expr>,
+    pub pad_with: Option<syn::Expr>,
+    pub strict: bool,
+    pub recursive: bool,
     pub tag: Option<Tag>,
     pub write_value: Option<syn::Expr>,
     pub other: bool,
     pub crate_path: Option<syn::Path>,
+    pub assert: Option<syn::Expr>,
+    pub map: Option<Map>,
+    pub default: Option<syn::Expr>,
+    pub until: Option<syn::Expr>,
+    pub terminator: Option<syn::Expr>,
+    pub include_terminator: Option<bool>,
+    pub write_terminator: Option<bool>,
+    pub item_tag: Option<syn::Expr>,
+    pub after_decode: Option<syn::Expr>,
+    pub before_encode: Option<syn::Expr>,
+    pub encode_tag: Option<syn::Expr>,
+    pub bit_order: Option<BitOrder>,
+    pub endian_from: Option<syn::Expr>,
+    pub ber: Option<Ber>,
+    pub format: Option<Format>,
+    pub field_number: Option<syn::Expr>,
 }
 
 pub enum Ctx {
@@ -37,10 +59,41 @@ pub enum Tag {
     },
 }
 
+pub enum Format {
+    Protobuf,
+}
+
+pub enum Ber {
+    Implicit(syn::Expr),
+    Explicit(syn::Expr),
+}
+
+#[allow(clippy::large_enum_variant)]
+pub enum Map {
+    Wire {
+        typ: syn::Type,
+        decode: Option<MapFn>,
+        encode: Option<MapFn>,
+    },
+    With(syn::Path),
+}
+
+#[derive(Clone, Copy)]
+pub enum BitOrder {
+    Lsb0,
+    Msb0,
+}
+
+pub enum MapFn {
+    Infallible(syn::Expr),
+    Fallible(syn::Expr),
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum AttrKind {
     Enum,
     Struct,
+    Union,
     Variant,
     Field,
 }
@@ -50,12 +103,29 @@ impl fmt::Display for AttrKind {
         match self {
             Self::Enum => write!(f, "enum"),
             Self::Struct => write!(f, "struct"),
+            Self::Union => write!(f, "union"),
             Self::Variant => write!(f, "variant"),
             Self::Field => write!(f, "field"),
         }
     }
 }
 
+fn bit_order_endianness(crate_path: &TokenStream, bit_order: BitOrder) -> TokenStream {
+    match bit_order {
+        BitOrder::Lsb0 => quote!(#crate_path::LittleEndian),
+        BitOrder::Msb0 => quote!(#crate_path::BigEndian),
+    }
+}
+
+/// Parses a flag that is either bare, meaning `true`, or followed by `= <bool>`.
+fn parse_flag(meta: &syn::meta::ParseNestedMeta) -> Result<bool> {
+    if meta.input.peek(Token![=]) {
+        Ok(meta.value()?.parse::<syn::LitBool>()?.value)
+    } else {
+        Ok(true)
+    }
+}
+
 macro_rules! expect_attr_kind {
     ($pat:pat, $kind:expr, $meta:expr) => {
         if let Some(kind) = $kind {
@@ -106,6 +176,116 @@ impl Attrs {
         }
     }
 
+    pub fn decode_assert(&self, name: &str) -> TokenStream {
+        if let Some(assert) = &self.assert {
+            let crate_path = self.crate_path();
+            let message = assert.to_token_stream().to_string();
+            quote!(
+                if !(#assert) {
+                    return ::core::result::Result::Err(#crate_path::Error::Validation {
+                        field: #name,
+                        message: #message,
+                    });
+                }
+            )
+        } else {
+            TokenStream::new()
+        }
+    }
+
+    pub const fn has_pad_pattern(&self) -> bool {
+        self.pad_value.is_some() || self.pad_with.is_some()
+    }
+
+    pub fn pad_pattern(&self) -> TokenStream {
+        if let Some(pad_with) = &self.pad_with {
+            quote!(::core::convert::AsRef::<[u8]>::as_ref(&#pad_with))
+        } else if let Some(pad_value) = &self.pad_value {
+            quote!(&[#pad_value])
+        } else {
+            quote!(&[0])
+        }
+    }
+
+    /// The bit order in which padding patterns are applied, which is that of `bit_order` if set
+    /// on the container.
+    pub fn pad_endianness(&self) -> TokenStream {
+        self.bit_order.map_or_else(
+            || quote!(__E),
+            |bit_order| bit_order_endianness(&self.crate_path(), bit_order),
+        )
+    }
+
+    pub fn decode_bit_order(&self) -> (TokenStream, TokenStream) {
+        self.bit_order.map_or_else(Default::default, |bit_order| {
+            let crate_path = self.crate_path();
+            let endianness = bit_order_endianness(&crate_path, bit_order);
+            (
+                quote!(
+                    if !#crate_path::BitRead::byte_aligned(__io_reader) {
+                        return ::core::result::Result::Err(#crate_path::Error::Unaligned);
+                    }
+                    let __io_reader = &mut #crate_path::bitstream_io::BitReader::endian(
+                        #crate_path::util::ByteReader(__io_reader),
+                        #endianness,
+                    );
+                ),
+                if self.strict {
+                    let pad_pattern = self.pad_pattern();
+                    quote!(
+                        #crate_path::util::decode_align::<_, #endianness>(
+                            __io_reader,
+                            #pad_pattern,
+                        )?;
+                    )
+                } else {
+                    quote!(#crate_path::BitRead::byte_align(__io_reader);)
+                },
+            )
+        })
+    }
+
+    pub fn encode_bit_order(&self) -> (TokenStream, TokenStream) {
+        self.bit_order.map_or_else(Default::default, |bit_order| {
+            let crate_path = self.crate_path();
+            let endianness = bit_order_endianness(&crate_path, bit_order);
+            (
+                quote!(
+                    if !#crate_path::BitWrite::byte_aligned(__io_writer) {
+                        return ::core::result::Result::Err(#crate_path::Error::Unaligned);
+                    }
+                    let __io_writer = &mut #crate_path::bitstream_io::BitWriter::endian(
+                        #crate_path::util::ByteWriter(__io_writer),
+                        #endianness,
+                    );
+                ),
+                if self.has_pad_pattern() {
+                    let pad_pattern = self.pad_pattern();
+                    quote!(
+                        #crate_path::util::encode_align::<_, #endianness>(
+                            __io_writer,
+                            #pad_pattern,
+                        )?;
+                    )
+                } else {
+                    quote!(#crate_path::BitWrite::byte_align(__io_writer)?;)
+                },
+            )
+        })
+    }
+
+    pub fn after_decode(&self) -> TokenStream {
+        self.after_decode
+            .as_ref()
+            .map_or_else(TokenStream::new, |after_decode| quote!(#after_decode;))
+    }
+
+    pub fn before_encode(&self) -> TokenStream {
+        self.before_encode
+            .as_ref()
+            .map_or_else(TokenStream::new, |before_encode| quote!(#before_encode;))
+    }
+
     pub fn crate_path(&self) -> TokenStream {
         if let Some(path) = &self.crate_path {
             quote!(#path)
@@ -124,6 +304,10 @@ impl Attrs {
         let mut attrs = if let Some(parent) = parent {
             Self {
                 crate_path: parent.crate_path.clone(),
+                strict: parent.strict,
+                bit_order: parent.bit_order,
+                pad_value: parent.pad_value.clone(),
+                pad_with: parent.pad_with.clone(),
                 ..Default::default()
             }
         } else {
@@ -134,10 +318,22 @@ impl Attrs {
         let mut tag_type = None;
         let mut tag_value = None;
         let mut tag_bits = None;
+        let mut implicit = None;
+        let mut explicit = None;
 
         let mut ctx = None;
         let mut ctx_bounds = None;
 
+        let mut pad_value = None;
+        let mut pad_with = None;
+
+        let mut wire_type = None;
+        let mut map = None;
+        let mut try_map = None;
+        let mut map_back = None;
+        let mut try_map_back = None;
+        let mut with = None;
+
         for attr in attribs {
             if attr.path().is_ident("bin_proto") {
                 attr.parse_nested_meta(|meta| {
@@ -151,7 +347,7 @@ impl Attrs {
                             attrs.untagged = true;
                         }
                         "discriminant_type" => {
-                            expect_attr_kind!(AttrKind::Enum, kind, meta);
+                            expect_attr_kind!(AttrKind::Enum | AttrKind::Union, kind, meta);
                             attrs.discriminant_type = Some(meta.value()?.parse()?);
                         }
                         "discriminant" => {
@@ -159,11 +355,23 @@ impl Attrs {
                             attrs.discriminant = Some(meta.value()?.parse()?);
                         }
                         "ctx" => {
-                            expect_attr_kind!(AttrKind::Enum | AttrKind::Struct, kind, meta);
-                            ctx = Some(meta.value()?.parse()?);
+                            if kind == Some(AttrKind::Field) {
+                                attrs.field_ctx = Some(meta.value()?.parse()?);
+                            } else {
+                                expect_attr_kind!(
+                                    AttrKind::Enum | AttrKind::Struct | AttrKind::Union,
+                                    kind,
+                                    meta
+                                );
+                                ctx = Some(meta.value()?.parse()?);
+                            }
                         }
                         "ctx_generics" => {
-                            expect_attr_kind!(AttrKind::Enum | AttrKind::Struct, kind, meta);
+                            expect_attr_kind!(
+                                AttrKind::Enum | AttrKind::Struct | AttrKind::Union,
+                                kind,
+                                meta
+                            );
                             let content;
                             parenthesized!(content in meta.input);
                             attrs.ctx_generics = Some(
@@ -175,7 +383,11 @@ impl Attrs {
                         );
                         }
                         "ctx_bounds" => {
-                            expect_attr_kind!(AttrKind::Enum | AttrKind::Struct, kind, meta);
+                            expect_attr_kind!(
+                                AttrKind::Enum | AttrKind::Struct | AttrKind::Union,
+                                kind,
+                                meta
+                            );
                             let content;
                             parenthesized!(content in meta.input);
                             ctx_bounds = Some(
@@ -223,6 +435,88 @@ impl Attrs {
                             attrs.skip_encode = true;
                             attrs.skip_decode = true;
                         }
+                        "until" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            attrs.until = Some(meta.value()?.parse()?);
+                        }
+                        "terminator" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            attrs.terminator = Some(meta.value()?.parse()?);
+                        }
+                        "include_terminator" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            attrs.include_terminator = Some(parse_flag(&meta)?);
+                        }
+                        "write_terminator" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            attrs.write_terminator = Some(parse_flag(&meta)?);
+                        }
+                        "item_tag" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            attrs.item_tag = Some(meta.value()?.parse()?);
+                        }
+                        "after_decode" => {
+                            expect_attr_kind!(
+                                AttrKind::Struct | AttrKind::Enum | AttrKind::Field,
+                                kind,
+                                meta
+                            );
+                            attrs.after_decode = Some(meta.value()?.parse()?);
+                        }
+                        "before_encode" => {
+                            expect_attr_kind!(
+                                AttrKind::Struct | AttrKind::Enum | AttrKind::Field,
+                                kind,
+                                meta
+                            );
+                            attrs.before_encode = Some(meta.value()?.parse()?);
+                        }
+                        "bit_order" => {
+                            expect_attr_kind!(AttrKind::Struct, kind, meta);
+                            let bit_order: syn::Ident = meta.value()?.parse()?;
+                            attrs.bit_order = Some(match bit_order.to_string().as_str() {
+                                "lsb0" => BitOrder::Lsb0,
+                                "msb0" => BitOrder::Msb0,
+                                _ => {
+                                    return Err(Error::new(
+                                        bit_order.span(),
+                                        "expected 'lsb0' or 'msb0'",
+                                    ))
+                                }
+                            });
+                        }
+                        "format" => {
+                            expect_attr_kind!(AttrKind::Struct, kind, meta);
+                            let format: syn::Ident = meta.value()?.parse()?;
+                            attrs.format = Some(match format.to_string().as_str() {
+                                "protobuf" => Format::Protobuf,
+                                _ => return Err(Error::new(format.span(), "expected 'protobuf'")),
+                            });
+                        }
+                        "field_number" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            attrs.field_number = Some(meta.value()?.parse()?);
+                        }
+                        "endian_from" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            attrs.endian_from = Some(meta.value()?.parse()?);
+                        }
+                        "implicit" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            implicit = Some(meta.value()?.parse()?);
+                        }
+                        "explicit" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            explicit = Some(meta.value()?.parse()?);
+                        }
+                        "encode_tag" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            attrs.encode_tag = Some(meta.value()?.parse()?);
+                        }
+                        "default" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            attrs.default = Some(meta.value()?.parse()?);
+                        }
                         "pad_before" => {
                             expect_attr_kind!(AttrKind::Struct | AttrKind::Field, kind, meta);
                             attrs.pad_before = Some(meta.value()?.parse()?);
@@ -231,6 +525,26 @@ impl Attrs {
                             expect_attr_kind!(AttrKind::Struct | AttrKind::Field, kind, meta);
                             attrs.pad_after = Some(meta.value()?.parse()?);
                         }
+                        "pad_value" => {
+                            expect_attr_kind!(AttrKind::Struct | AttrKind::Field, kind, meta);
+                            pad_value = Some(meta.value()?.parse()?);
+                        }
+                        "pad_with" => {
+                            expect_attr_kind!(AttrKind::Struct | AttrKind::Field, kind, meta);
+                            pad_with = Some(meta.value()?.parse()?);
+                        }
+                        "strict" => {
+                            expect_attr_kind!(
+                                AttrKind::Enum | AttrKind::Struct | AttrKind::Field,
+                                kind,
+                                meta
+                            );
+                            attrs.strict = true;
+                        }
+                        "recursive" => {
+                            expect_attr_kind!(AttrKind::Enum | AttrKind::Struct, kind, meta);
+                            attrs.recursive = true;
+                        }
                         "magic" => {
                             expect_attr_kind!(AttrKind::Struct | AttrKind::Field, kind, meta);
                             attrs.magic = Some(meta.value()?.parse()?);
@@ -239,8 +553,40 @@ impl Attrs {
                             expect_attr_kind!(AttrKind::Variant, kind, meta);
                             attrs.other = true;
                         }
+                        "assert" => {
+                            expect_attr_kind!(AttrKind::Struct | AttrKind::Field, kind, meta);
+                            attrs.assert = Some(meta.value()?.parse()?);
+                        }
+                        "wire_type" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            wire_type = Some(meta.value()?.parse()?);
+                        }
+                        "map" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            map = Some(meta.value()?.parse()?);
+                        }
+                        "try_map" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            try_map = Some(meta.value()?.parse()?);
+                        }
+                        "map_back" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            map_back = Some(meta.value()?.parse()?);
+                        }
+                        "try_map_back" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            try_map_back = Some(meta.value()?.parse()?);
+                        }
+                        "with" => {
+                            expect_attr_kind!(AttrKind::Field, kind, meta);
+                            with = Some(meta.value()?.parse()?);
+                        }
                         "crate" => {
-                            expect_attr_kind!(AttrKind::Enum | AttrKind::Struct, kind, meta);
+                            expect_attr_kind!(
+                                AttrKind::Enum | AttrKind::Struct | AttrKind::Union,
+                                kind,
+                                meta
+                            );
                             attrs.crate_path = Some(meta.value()?.parse()?);
                         }
                         _ => {
@@ -288,15 +634,157 @@ impl Attrs {
             }
         }
 
-        if [attrs.bits.is_some(), attrs.untagged, attrs.tag.is_some()]
-            .iter()
-            .filter(|b| **b)
-            .count()
+        match (implicit, explicit) {
+            (Some(implicit), None) => attrs.ber = Some(Ber::Implicit(implicit)),
+            (None, Some(explicit)) => attrs.ber = Some(Ber::Explicit(explicit)),
+            (None, None) => {}
+            _ => {
+                return Err(Error::new(
+                    span,
+                    "use of mutually exclusive 'implicit' and 'explicit' attributes.",
+                ));
+            }
+        }
+
+        let map_fn = |infallible, fallible| match (infallible, fallible) {
+            (Some(map), None) => Ok(Some(MapFn::Infallible(map))),
+            (None, Some(try_map)) => Ok(Some(MapFn::Fallible(try_map))),
+            (None, None) => Ok(None),
+            _ => Err(Error::new(
+                span,
+                "use of mutually exclusive infallible and fallible map attributes.",
+            )),
+        };
+        let decode = map_fn(map, try_map)?;
+        let encode = map_fn(map_back, try_map_back)?;
+        match (wire_type, decode, encode, with) {
+            (Some(typ), decode, encode, None) => {
+                attrs.map = Some(Map::Wire {
+                    typ,
+                    decode,
+                    encode,
+                });
+            }
+            (None, None, None, Some(with)) => attrs.map = Some(Map::With(with)),
+            (None, None, None, None) => {}
+            _ => {
+                return Err(Error::new(
+                    span,
+                    "invalid configuration of 'wire_type', 'map', 'try_map', 'map_back', \
+                    'try_map_back', or 'with' attributes.",
+                ));
+            }
+        }
+
+        if [
+            attrs.bits.is_some(),
+            attrs.untagged,
+            attrs.tag.is_some(),
+            attrs.until.is_some(),
+            attrs.terminator.is_some(),
+            attrs.ber.is_some(),
+        ]
+        .iter()
+        .filter(|b| **b)
+        .count()
             > 1
         {
             return Err(Error::new(
                 span,
-                "bits, untagged, and tag are mutually-exclusive attributes",
+                "bits, untagged, tag, until, terminator, implicit, and explicit are mutually-exclusive \
+                attributes",
+            ));
+        }
+
+        if attrs.ber.is_some() && attrs.map.is_some() {
+            return Err(Error::new(
+                span,
+                "implicit and explicit cannot be combined with wire_type or with",
+            ));
+        }
+
+        if pad_value.is_some() && pad_with.is_some() {
+            return Err(Error::new(
+                span,
+                "pad_value and pad_with are mutually-exclusive attributes",
+            ));
+        }
+        if pad_value.is_some() || pad_with.is_some() {
+            attrs.pad_value = pad_value;
+            attrs.pad_with = pad_with;
+        }
+
+        if attrs.encode_tag.is_some()
+            && (matches!(attrs.tag, Some(Tag::Prepend { .. }))
+                || attrs.bits.is_some()
+                || attrs.ber.is_some())
+        {
+            return Err(Error::new(
+                span,
+                "encode_tag cannot be combined with tag_type, bits, implicit, or explicit",
+            ));
+        }
+
+        if attrs.item_tag.is_some()
+            && (attrs.bits.is_some() || attrs.until.is_some() || attrs.terminator.is_some())
+        {
+            return Err(Error::new(
+                span,
+                "item_tag cannot be combined with bits, until, or terminator",
+            ));
+        }
+
+        if attrs.item_tag.is_some() && attrs.tag.is_none() && !attrs.untagged {
+            return Err(Error::new(
+                span,
+                "item_tag requires tag, tag_type, or untagged",
+            ));
+        }
+
+        if (attrs.include_terminator.is_some() || attrs.write_terminator.is_some())
+            && attrs.until.is_none()
+            && attrs.terminator.is_none()
+        {
+            return Err(Error::new(
+                span,
+                "include_terminator and write_terminator require until or terminator",
+            ));
+        }
+
+        if attrs.until.is_some()
+            && attrs.include_terminator == Some(false)
+            && attrs.write_terminator == Some(true)
+        {
+            return Err(Error::new(
+                span,
+                "write_terminator requires include_terminator when combined with until, which has \
+                no terminator value to write",
+            ));
+        }
+
+        if attrs.write_terminator == Some(false)
+            && attrs
+                .include_terminator
+                .unwrap_or_else(|| attrs.until.is_some())
+        {
+            return Err(Error::new(
+                span,
+                "write_terminator = false cannot be combined with an included terminator, which \
+                would be decoded but not encoded",
+            ));
+        }
+
+        if attrs.field_number.is_some() && parent.is_some_and(|parent| parent.format.is_none()) {
+            return Err(Error::new(
+                span,
+                "'field_number' requires 'format' on the struct",
+            ));
+        }
+
+        if attrs.default.is_some() && !attrs.skip_decode {
+            return Err(Error::new(
+                span,
+                "'default' requires 'skip_decode' or 'skip' attribute",
             ));
         }
 
@@ -0,0 +1,273 @@
+use crate::{
+    attr::{AttrKind, Attrs, Map, Tag},
+    enums::Enum,
+};
+use proc_macro2::{Span, TokenStream};
+use syn::{spanned::Spanned, Error, Result};
+
+struct SchemaField {
+    name: String,
+    schema: TokenStream,
+    magic: Option<syn::Expr>,
+    pad_before: Option<syn::Expr>,
+    pad_after: Option<syn::Expr>,
+}
+
+pub fn struct_schema(attrs: &Attrs, fields: &syn::Fields) -> Result<TokenStream> {
+    let crate_path = attrs.crate_path();
+    let mut schema_fields = fields_schema(attrs, fields)?;
+
+    // Container-level magic and padding are folded into the first and last fields, which encode
+    // them in the same order, or otherwise described by an unnamed field without a value.
+    let empty = || SchemaField {
+        name: String::new(),
+        schema: quote!(#crate_path::dynamic::Schema::Struct(::core::default::Default::default())),
+        magic: None,
+        pad_before: None,
+        pad_after: None,
+    };
+    if attrs.magic.is_some() || attrs.pad_before.is_some() {
+        if schema_fields
+            .first()
+            .is_none_or(|first| first.magic.is_some() || first.pad_before.is_some())
+        {
+            schema_fields.insert(0, empty());
+        }
+        schema_fields[0].magic.clone_from(&attrs.magic);
+        schema_fields[0].pad_before.clone_from(&attrs.pad_before);
+    }
+    if attrs.pad_after.is_some() {
+        if schema_fields
+            .last()
+            .is_none_or(|last| last.pad_after.is_some())
+        {
+            schema_fields.push(empty());
+        }
+        if let Some(last) = schema_fields.last_mut() {
+            last.pad_after.clone_from(&attrs.pad_after);
+        }
+    }
+
+    let schema_fields = schema_fields
+        .iter()
+        .map(|field| field.to_tokens(&crate_path));
+    Ok(quote!(
+        #crate_path::dynamic::Schema::Struct(::core::convert::From::from([#( #schema_fields ),*]))
+    ))
+}
+
+pub fn enum_schema(attrs: &Attrs, plan: &Enum) -> Result<TokenStream> {
+    let crate_path = attrs.crate_path();
+    let discriminant_ty = &plan.discriminant_ty;
+    let discriminant = if let Some(bits) = &attrs.bits {
+        bits_schema(&crate_path, bits, &quote!(#discriminant_ty))
+    } else {
+        quote!(<#discriminant_ty as #crate_path::dynamic::Describe>::schema())
+    };
+
+    let mut variants = Vec::new();
+    for variant in &plan.variants {
+        let span = variant.ident.span();
+        if variant.discriminant_other {
+            return Err(unsupported(span, "other"));
+        }
+        if variant.skip_encode != variant.skip_decode {
+            return Err(Error::new(
+                span,
+                "cannot derive Describe with only one of 'skip_encode' and 'skip_decode'",
+            ));
+        }
+        if variant.skip_encode {
+            continue;
+        }
+        let discriminant = variant
+            .discriminant_value
+            .as_ref()
+            .ok_or_else(|| Error::new(span, "missing discriminant"))?;
+        let name = variant.ident.to_string();
+        let fields = fields_schema(attrs, &variant.fields)?;
+        let fields = fields.iter().map(|field| field.to_tokens(&crate_path));
+        variants.push(quote!(
+            #crate_path::dynamic::Variant {
+                name: ::core::convert::From::from(#name),
+                discriminant: #crate_path::dynamic::Value::from({
+                    let discriminant: #discriminant_ty = #discriminant;
+                    discriminant
+                }),
+                fields: ::core::convert::From::from([#( #fields ),*]),
+            }
+        ));
+    }
+
+    Ok(quote!(
+        #crate_path::dynamic::Schema::Enum {
+            discriminant: ::core::convert::From::from(#discriminant),
+            variants: ::core::convert::From::from([#( #variants ),*]),
+        }
+    ))
+}
+
+fn fields_schema(parent_attrs: &Attrs, fields: &syn::Fields) -> Result<Vec<SchemaField>> {
+    let crate_path = parent_attrs.crate_path();
+    let mut previous = Vec::new();
+    let mut schema_fields = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let attrs = Attrs::parse(
+            Some(parent_attrs),
+            field.attrs.as_slice(),
+            Some(AttrKind::Field),
+            field.span(),
+        )?;
+        let name = field
+            .ident
+            .as_ref()
+            .map_or_else(|| index.to_string(), ToString::to_string);
+        let binding = field
+            .ident
+            .as_ref()
+            .map_or_else(|| format!("field_{index}"), ToString::to_string);
+        check_supported(&attrs, field.span())?;
+        if attrs.skip_encode != attrs.skip_decode {
+            return Err(Error::new(
+                field.span(),
+                "cannot derive Describe with only one of 'skip_encode' and 'skip_decode'",
+            ));
+        }
+        if attrs.skip_encode {
+            continue;
+        }
+
+        let ty = if let Some(Map::Wire { typ, .. }) = &attrs.map {
+            quote!(#typ)
+        } else {
+            let ty = &field.ty;
+            quote!(#ty)
+        };
+        let length = match &attrs.tag {
+            Some(Tag::External(tag)) => {
+                let name = tag_field(tag, &previous)?;
+                Some(quote!(#crate_path::dynamic::Length::Field(
+                    ::core::convert::From::from(#name)
+                )))
+            }
+            Some(Tag::Prepend { typ, bits, .. }) => {
+                let prefix = bits.as_ref().map_or_else(
+                    || quote!(<#typ as #crate_path::dynamic::Describe>::schema()),
+                    |bits| bits_schema(&crate_path, bits, &quote!(#typ)),
+                );
+                Some(quote!(#crate_path::dynamic::Length::Prefix(
+                    ::core::convert::From::from(#prefix)
+                )))
+            }
+            None if attrs.untagged => Some(quote!(#crate_path::dynamic::Length::Untagged)),
+            None => None,
+        };
+        let schema = if let Some(length) = length {
+            quote!(<#ty as #crate_path::dynamic::DescribeTagged>::tagged_schema(#length))
+        } else if let Some(bits) = &attrs.bits {
+            bits_schema(&crate_path, bits, &ty)
+        } else {
+            quote!(<#ty as #crate_path::dynamic::Describe>::schema())
+        };
+
+        previous.push((binding, name.clone()));
+        schema_fields.push(SchemaField {
+            name,
+            schema,
+            magic: attrs.magic,
+            pad_before: attrs.pad_before,
+            pad_after: attrs.pad_after,
+        });
+    }
+    Ok(schema_fields)
+}
+
+impl SchemaField {
+    fn to_tokens(&self, crate_path: &TokenStream) -> TokenStream {
+        let Self {
+            name,
+            schema,
+            magic,
+            pad_before,
+            pad_after,
+        } = self;
+        let magic = magic.as_ref().map_or_else(
+            || quote!(::core::default::Default::default()),
+            |magic| {
+                quote!(::core::convert::From::from(
+                    ::core::convert::AsRef::<[u8]>::as_ref(#magic)
+                ))
+            },
+        );
+        let pad_before = pad_before
+            .as_ref()
+            .map_or_else(|| quote!(0), |pad| quote!(#pad));
+        let pad_after = pad_after
+            .as_ref()
+            .map_or_else(|| quote!(0), |pad| quote!(#pad));
+        quote!(
+            #crate_path::dynamic::Field {
+                name: ::core::convert::From::from(#name),
+                schema: #schema,
+                magic: #magic,
+                pad_before: #pad_before,
+                pad_after: #pad_after,
+            }
+        )
+    }
+}
+
+fn bits_schema(crate_path: &TokenStream, bits: &syn::Expr, ty: &TokenStream) -> TokenStream {
+    quote!(
+        #crate_path::dynamic::Schema::Bits {
+            width: #bits,
+            signed: <#ty as #crate_path::dynamic::Describe>::schema().is_signed(),
+        }
+    )
+}
+
+/// Returns an error if the attributes use an option that a `Schema` cannot express.
+pub fn check_supported(attrs: &Attrs, span: Span) -> Result<()> {
+    let options = [
+        (attrs.bit_order.is_some(), "bit_order"),
+        (attrs.endian_from.is_some(), "endian_from"),
+        (matches!(attrs.map, Some(Map::With(_))), "with"),
+        (attrs.pad_value.is_some(), "pad_value"),
+        (attrs.pad_with.is_some(), "pad_with"),
+        (attrs.item_tag.is_some(), "item_tag"),
+        (attrs.until.is_some(), "until"),
+        (attrs.terminator.is_some(), "terminator"),
+        (attrs.ber.is_some(), "implicit or explicit"),
+    ];
+    options
+        .into_iter()
+        .find(|(used, _)| *used)
+        .map_or(Ok(()), |(_, option)| Err(unsupported(span, option)))
+}
+
+fn unsupported(span: Span, option: &str) -> Error {
+    Error::new(span, format!("cannot derive Describe with '{option}'"))
+}
+
+/// Finds the previous field that an external tag refers to. The tag must be the field, optionally
+/// followed by an `as` cast.
+fn tag_field(tag: &syn::Expr, previous: &[(String, String)]) -> Result<String> {
+    let field = match tag {
+        syn::Expr::Cast(cast) => &*cast.expr,
+        tag => tag,
+    };
+    let ident = match field {
+        syn::Expr::Path(path) if path.qself.is_none() => path.path.get_ident(),
+        _ => None,
+    };
+    ident
+        .and_then(|ident| previous.iter().find(|(binding, _)| ident == binding))
+        .map(|(_, name)| name.clone())
+        .ok_or_else(|| {
+            Error::new(
+                tag.span(),
+                "cannot derive Describe unless 'tag' is a previous field, optionally followed by \
+                an 'as' cast",
+            )
+        })
+}
@@ -53,7 +53,7 @@ pub fn encode_variant_fields(plan: &enums::Enum) -> Result<TokenStream> {
             let encodes = if variant.skip_encode {
                 quote!(return ::core::result::Result::Err(#crate_path::Error::EncodeSkipped))
             } else {
-                codegen::encodes(plan.parent_attrs, &variant.fields, false)?
+                codegen::encodes(plan.parent_attrs, &variant.fields)?
             };
 
             Ok(quote!(Self :: #fields_pattern => {
@@ -96,6 +96,30 @@ pub fn variant_discriminant(plan: &enums::Enum) -> Result<TokenStream> {
     }))
 }
 
+pub fn variant_decodable(plan: &enums::Enum) -> TokenStream {
+    let decodable = plan.variants.iter().filter(|variant| !variant.skip_decode);
+    if decodable.clone().any(|variant| variant.discriminant_other) {
+        return quote!({
+            let _ = discriminant;
+            true
+        });
+    }
+    let discriminants: Vec<_> = decodable
+        .filter_map(|variant| variant.discriminant_value.as_ref())
+        .collect();
+    if discriminants.is_empty() {
+        quote!({
+            let _ = discriminant;
+            false
+        })
+    } else {
+        quote!(match *discriminant {
+            #(#discriminants => true,)*
+            _ => false,
+        })
+    }
+}
+
 pub fn decode_variant_fields(plan: &enums::Enum) -> Result<TokenStream> {
     let crate_path = plan.parent_attrs.crate_path();
     let discriminant_match_branches = plan
@@ -1,29 +1,26 @@
+pub mod describe;
 pub mod enums;
+pub mod protobuf;
 pub mod trait_impl;
+pub mod unions;
 
-use crate::attr::{AttrKind, Attrs, Tag};
-use proc_macro2::TokenStream;
+use crate::attr::{AttrKind, Attrs, Ber, Map, MapFn, Tag};
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::ToTokens;
 use syn::{spanned::Spanned, Error, Result};
 
 pub fn decodes(parent_attrs: &Attrs, fields: &syn::Fields) -> Result<(TokenStream, TokenStream)> {
     match fields {
         syn::Fields::Named(fields) => decode_named_fields(parent_attrs, fields),
-        syn::Fields::Unnamed(fields) => Ok((
-            TokenStream::new(),
-            decode_unnamed_fields(parent_attrs, fields)?,
-        )),
+        syn::Fields::Unnamed(fields) => decode_unnamed_fields(parent_attrs, fields),
         syn::Fields::Unit => Ok((TokenStream::new(), TokenStream::new())),
     }
 }
 
-pub fn encodes(
-    parent_attrs: &Attrs,
-    fields: &syn::Fields,
-    self_prefix: bool,
-) -> Result<TokenStream> {
+pub fn encodes(parent_attrs: &Attrs, fields: &syn::Fields) -> Result<TokenStream> {
     match fields {
-        syn::Fields::Named(fields) => encode_named_fields(parent_attrs, fields, self_prefix),
-        syn::Fields::Unnamed(fields) => encode_unnamed_fields(parent_attrs, fields, self_prefix),
+        syn::Fields::Named(fields) => encode_named_fields(parent_attrs, fields),
+        syn::Fields::Unnamed(fields) => encode_unnamed_fields(parent_attrs, fields),
         syn::Fields::Unit => Ok(TokenStream::new()),
     }
 }
@@ -36,14 +33,12 @@ fn decode_named_fields(
         .named
         .iter()
         .map(|field| {
-            let field_name = &field.ident;
-            let field_ty = &field.ty;
-
-            let decode = decode(parent_attrs, field)?;
+            let field_name = field
+                .ident
+                .as_ref()
+                .ok_or_else(|| Error::new(field.span(), "expected named field"))?;
 
-            Ok(quote!(
-                let #field_name : #field_ty = #decode;
-            ))
+            decode(parent_attrs, field, field_name, &field_name.to_string())
         })
         .collect::<Result<Vec<_>>>()?;
 
@@ -63,74 +58,272 @@ fn decode_named_fields(
     ))
 }
 
-pub fn decode_pad(crate_path: &TokenStream, pad: &syn::Expr) -> TokenStream {
-    quote!(#crate_path::BitRead::skip(__io_reader, #pad)?;)
+/// Guards the nesting depth when decoding a recursive type, which has the `recursive` attribute or
+/// fields whose types refer to the type itself.
+pub fn decode_depth_guard<'a>(
+    attrs: &Attrs,
+    ident: &syn::Ident,
+    mut fields: impl Iterator<Item = &'a syn::Field>,
+) -> TokenStream {
+    if attrs.recursive || fields.any(|field| refers_to(field.ty.to_token_stream(), ident)) {
+        let crate_path = attrs.crate_path();
+        quote!(let __depth = #crate_path::limits::enter()?;)
+    } else {
+        TokenStream::new()
+    }
+}
+
+fn refers_to(tokens: TokenStream, ident: &syn::Ident) -> bool {
+    tokens.into_iter().any(|tree| match tree {
+        TokenTree::Ident(other) => other == *ident || other == "Self",
+        TokenTree::Group(group) => refers_to(group.stream(), ident),
+        _ => false,
+    })
+}
+
+pub fn decode_pad(attrs: &Attrs, pad: &syn::Expr) -> TokenStream {
+    let crate_path = attrs.crate_path();
+    if attrs.strict {
+        let pad_pattern = attrs.pad_pattern();
+        let endianness = attrs.pad_endianness();
+        quote!(#crate_path::util::decode_pad::<_, #endianness>(__io_reader, #pad, #pad_pattern)?;)
+    } else {
+        quote!(#crate_path::BitRead::skip(__io_reader, #pad)?;)
+    }
 }
 
-fn decode(parent_attrs: &Attrs, field: &syn::Field) -> Result<TokenStream> {
+fn decode(
+    parent_attrs: &Attrs,
+    field: &syn::Field,
+    binding: &syn::Ident,
+    name: &str,
+) -> Result<TokenStream> {
     let attrs = Attrs::parse(
         Some(parent_attrs),
         field.attrs.as_slice(),
         Some(AttrKind::Field),
         field.span(),
     )?;
+    let field_ty = &field.ty;
+    let assert = attrs.decode_assert(name);
+    let after_decode = attrs.after_decode();
 
     if attrs.skip_decode {
-        return Ok(quote!(::core::default::Default::default()));
+        let default = attrs.default.map_or_else(
+            || quote!(::core::default::Default::default()),
+            |default| quote!(#default),
+        );
+        return Ok(quote!(
+            let #binding: #field_ty = #default;
+            #assert
+            #after_decode
+        ));
     }
 
     let crate_path = attrs.crate_path();
 
-    let pad_before = attrs
-        .pad_before
-        .as_ref()
-        .map(|pad| decode_pad(&crate_path, pad));
-    let pad_after = attrs
-        .pad_after
-        .as_ref()
-        .map(|pad| decode_pad(&crate_path, pad));
+    let pad_before = attrs.pad_before.as_ref().map(|pad| decode_pad(&attrs, pad));
+    let pad_after = attrs.pad_after.as_ref().map(|pad| decode_pad(&attrs, pad));
     let magic = attrs.decode_magic();
 
-    let decode = if let Some(Tag::Prepend { typ, bits, .. }) = attrs.tag {
+    let decode = with_endian_from(&crate_path, attrs.endian_from.as_ref(), |endianness| {
+        decode_codec(&attrs, field, endianness)
+    })?;
+
+    Ok(quote!(
+        let #binding: #field_ty = {
+            #pad_before
+            #magic
+            let decoded = #decode;
+            #pad_after
+            decoded
+        };
+        #assert
+        #after_decode
+    ))
+}
+
+/// Decodes the value of a field, with `endianness` as the byte order.
+fn decode_codec(
+    attrs: &Attrs,
+    field: &syn::Field,
+    endianness: &TokenStream,
+) -> Result<TokenStream> {
+    let crate_path = attrs.crate_path();
+    let decode = if let Some(Tag::Prepend { typ, bits, .. }) = &attrs.tag {
         let tag = if let Some(bits) = bits {
             quote!(#crate_path::Bits::<#bits>)
         } else {
             quote!(())
         };
+        let decode_value = decode_value(
+            &crate_path,
+            endianness,
+            attrs.map.as_ref(),
+            &decode_item_tag(
+                &crate_path,
+                attrs.item_tag.as_ref(),
+                quote!(#crate_path::Tag(__tag)),
+            ),
+            field.span(),
+        )?;
         quote!({
-            let __tag: #typ = #crate_path::BitDecode::decode::<_, __E>(__io_reader, __ctx, #tag)?;
-            #crate_path::BitDecode::decode::<_, __E>(
-                __io_reader,
-                __ctx,
-                #crate_path::Tag(__tag)
-            )?
+            let __tag: #typ = #crate_path::BitDecode::decode::<_, #endianness>(__io_reader, __ctx, #tag)?;
+            #decode_value
         })
+    } else if let Some(ber) = &attrs.ber {
+        let (tag, number) = ber_tag(&crate_path, ber);
+        quote!(#tag(#number).decode::<_, #endianness, _, _>(__io_reader, __ctx)?)
     } else {
-        let tag = if let Some(field_width) = attrs.bits {
+        let tag = if let Some(field_width) = &attrs.bits {
             quote!(#crate_path::Bits::<#field_width>)
         } else if attrs.untagged {
             quote!(#crate_path::Untagged)
-        } else if let Some(Tag::External(tag)) = attrs.tag {
+        } else if let Some(Tag::External(tag)) = &attrs.tag {
             quote!(#crate_path::Tag(#tag))
+        } else if let Some(until) = &attrs.until {
+            let until = quote!(#crate_path::Until(#until));
+            delimited(&crate_path, attrs, &until, until.clone())
+        } else if let Some(terminator) = &attrs.terminator {
+            let terminator = quote!(#crate_path::Terminated(#terminator));
+            delimited(&crate_path, attrs, &terminator, terminator.clone())
         } else {
             quote!(())
         };
-        quote!(#crate_path::BitDecode::decode::<_, __E>(__io_reader, __ctx, #tag)?)
+        let tag = decode_item_tag(&crate_path, attrs.item_tag.as_ref(), tag);
+        decode_value(
+            &crate_path,
+            endianness,
+            attrs.map.as_ref(),
+            &tag,
+            field.span(),
+        )?
     };
 
-    Ok(quote!({
-        #pad_before
-        #magic
-        let decoded = #decode;
-        #pad_after
-        decoded
-    }))
+    Ok(with_field_ctx(attrs.field_ctx.as_ref(), decode))
 }
 
-pub fn encode_pad(crate_path: &TokenStream, pad: &syn::Expr) -> TokenStream {
-    quote!(#crate_path::BitWrite::pad(__io_writer, #pad)?;)
+fn with_field_ctx(field_ctx: Option<&syn::Expr>, codec: TokenStream) -> TokenStream {
+    if let Some(field_ctx) = field_ctx {
+        quote!({
+            let __ctx = #field_ctx;
+            #codec
+        })
+    } else {
+        codec
+    }
+}
+
+/// Generates the codec for each byte order selected by `endian_from` at runtime, or otherwise
+/// for the `__E` type parameter.
+fn with_endian_from(
+    crate_path: &TokenStream,
+    endian_from: Option<&syn::Expr>,
+    codec: impl Fn(&TokenStream) -> Result<TokenStream>,
+) -> Result<TokenStream> {
+    Ok(if let Some(endian_from) = endian_from {
+        let big = codec(&quote!(#crate_path::BigEndian))?;
+        let little = codec(&quote!(#crate_path::LittleEndian))?;
+        quote!(
+            match #crate_path::DynEndian::from(#endian_from) {
+                #crate_path::DynEndian::Big => #big,
+                #crate_path::DynEndian::Little => #little,
+            }
+        )
+    } else {
+        codec(&quote!(__E))?
+    })
+}
+
+/// Wraps the `Until` or `Terminated` tag in `Delimited` if `include_terminator` or
+/// `write_terminator` is set, or otherwise returns `plain`.
+fn delimited(
+    crate_path: &TokenStream,
+    attrs: &Attrs,
+    delimiter: &TokenStream,
+    plain: TokenStream,
+) -> TokenStream {
+    if attrs.include_terminator.is_none() && attrs.write_terminator.is_none() {
+        return plain;
+    }
+    let include = attrs
+        .include_terminator
+        .unwrap_or_else(|| attrs.until.is_some());
+    let write = attrs
+        .write_terminator
+        .unwrap_or_else(|| include || attrs.terminator.is_some());
+    quote!(#crate_path::Delimited {
+        delimiter: #delimiter,
+        include: #include,
+        write: #write,
+    })
+}
+
+fn ber_tag<'a>(crate_path: &TokenStream, ber: &'a Ber) -> (TokenStream, &'a syn::Expr) {
+    match ber {
+        Ber::Implicit(number) => (quote!(#crate_path::ber::Implicit), number),
+        Ber::Explicit(number) => (quote!(#crate_path::ber::Explicit), number),
+    }
 }
 
+fn decode_item_tag(
+    crate_path: &TokenStream,
+    item_tag: Option<&syn::Expr>,
+    tag: TokenStream,
+) -> TokenStream {
+    if let Some(item_tag) = item_tag {
+        quote!(#crate_path::TagItems(#tag, |__index: usize| #crate_path::Tag(#item_tag)))
+    } else {
+        tag
+    }
+}
+
+fn decode_value(
+    crate_path: &TokenStream,
+    endianness: &TokenStream,
+    map: Option<&Map>,
+    tag: &TokenStream,
+    span: Span,
+) -> Result<TokenStream> {
+    Ok(match map {
+        Some(Map::Wire { typ, decode, .. }) => {
+            let map = match decode {
+                Some(MapFn::Infallible(map)) => quote!((#map)(__wire)),
+                Some(MapFn::Fallible(try_map)) => quote!((#try_map)(__wire)?),
+                None => {
+                    return Err(Error::new(
+                        span,
+                        "Wire type must specify 'map' or 'try_map'",
+                    ))
+                }
+            };
+            quote!({
+                let __wire: #typ =
+                    #crate_path::BitDecode::decode::<_, #endianness>(__io_reader, __ctx, #tag)?;
+                #map
+            })
+        }
+        Some(Map::With(with)) => {
+            quote!(#with::decode::<_, #endianness>(__io_reader, __ctx, #tag)?)
+        }
+        None => {
+            quote!(#crate_path::BitDecode::decode::<_, #endianness>(__io_reader, __ctx, #tag)?)
+        }
+    })
+}
+
+pub fn encode_pad(attrs: &Attrs, pad: &syn::Expr) -> TokenStream {
+    let crate_path = attrs.crate_path();
+    if attrs.has_pad_pattern() {
+        let pad_pattern = attrs.pad_pattern();
+        let endianness = attrs.pad_endianness();
+        quote!(#crate_path::util::encode_pad::<_, #endianness>(__io_writer, #pad, #pad_pattern)?;)
+    } else {
+        quote!(#crate_path::BitWrite::pad(__io_writer, #pad)?;)
+    }
+}
+
+#[allow(clippy::too_many_lines)]
 fn encode(parent: &Attrs, field: &syn::Field, field_name: &TokenStream) -> Result<TokenStream> {
     let attrs = Attrs::parse(
         Some(parent),
@@ -145,17 +338,12 @@ fn encode(parent: &Attrs, field: &syn::Field, field_name: &TokenStream) -> Resul
 
     let crate_path = attrs.crate_path();
 
-    let pad_before = attrs
-        .pad_before
-        .as_ref()
-        .map(|pad| encode_pad(&crate_path, pad));
-    let pad_after = attrs
-        .pad_after
-        .as_ref()
-        .map(|pad| encode_pad(&crate_path, pad));
+    let pad_before = attrs.pad_before.as_ref().map(|pad| encode_pad(&attrs, pad));
+    let pad_after = attrs.pad_after.as_ref().map(|pad| encode_pad(&attrs, pad));
     let magic = attrs.encode_magic();
+    let before_encode = attrs.before_encode();
 
-    let field_ref = if let Some(value) = attrs.write_value {
+    let field_ref = if let Some(value) = &attrs.write_value {
         let ty = &field.ty;
         quote!(&{
             let value: #ty = {#value};
@@ -165,11 +353,32 @@ fn encode(parent: &Attrs, field: &syn::Field, field_name: &TokenStream) -> Resul
         field_name.clone()
     };
 
+    let encode = with_endian_from(&crate_path, attrs.endian_from.as_ref(), |endianness| {
+        encode_codec(&attrs, field, &field_ref, endianness)
+    })?;
+
+    Ok(quote!(
+        #before_encode
+        #pad_before
+        #magic
+        #encode;
+        #pad_after
+    ))
+}
+
+/// Encodes the value of a field, with `endianness` as the byte order.
+fn encode_codec(
+    attrs: &Attrs,
+    field: &syn::Field,
+    field_ref: &TokenStream,
+    endianness: &TokenStream,
+) -> Result<TokenStream> {
+    let crate_path = attrs.crate_path();
     let encode = if let Some(Tag::Prepend {
         typ,
         write_value,
         bits,
-    }) = attrs.tag
+    }) = &attrs.tag
     {
         let Some(write_value) = write_value else {
             return Err(Error::new(field.span(), "Tag must specify 'write_value'"));
@@ -179,64 +388,126 @@ fn encode(parent: &Attrs, field: &syn::Field, field_name: &TokenStream) -> Resul
         } else {
             quote!(())
         };
+        let encode_value = encode_value(
+            &crate_path,
+            endianness,
+            attrs.map.as_ref(),
+            field_ref,
+            &encode_item_tag(
+                &crate_path,
+                attrs.item_tag.is_some(),
+                quote!(#crate_path::Untagged),
+            ),
+            field.span(),
+        )?;
         quote!(
             {
-                <#typ as #crate_path::BitEncode::<_, _>>::encode::<_, __E>(
+                <#typ as #crate_path::BitEncode::<_, _>>::encode::<_, #endianness>(
                     &{#write_value},
                     __io_writer,
                     __ctx,
                     #tag
                 )?;
-                #crate_path::BitEncode::encode::<_, __E>(
-                    #field_ref,
-                    __io_writer,
-                    __ctx,
-                    #crate_path::Untagged
-                )?
+                #encode_value
             }
         )
+    } else if let Some(ber) = &attrs.ber {
+        let (tag, number) = ber_tag(&crate_path, ber);
+        quote!(#tag(#number).encode::<_, #endianness, _, _>(#field_ref, __io_writer, __ctx)?)
     } else {
-        let tag = if let Some(field_width) = attrs.bits {
+        let tag = if let Some(encode_tag) = &attrs.encode_tag {
+            quote!(#encode_tag)
+        } else if let Some(field_width) = &attrs.bits {
             quote!(#crate_path::Bits::<#field_width>)
         } else if matches!(attrs.tag, Some(Tag::External(_))) || attrs.untagged {
             quote!(#crate_path::Untagged)
+        } else if let Some(until) = &attrs.until {
+            let until = quote!(#crate_path::Until(#until));
+            delimited(&crate_path, attrs, &until, quote!(#crate_path::Untagged))
+        } else if let Some(terminator) = &attrs.terminator {
+            let terminator = quote!(#crate_path::Terminated(#terminator));
+            delimited(&crate_path, attrs, &terminator, terminator.clone())
         } else {
             quote!(())
         };
+        let tag = encode_item_tag(&crate_path, attrs.item_tag.is_some(), tag);
+        let encode_value = encode_value(
+            &crate_path,
+            endianness,
+            attrs.map.as_ref(),
+            field_ref,
+            &tag,
+            field.span(),
+        )?;
         quote!(
             {
-                #crate_path::BitEncode::encode::<_, __E>(#field_ref, __io_writer, __ctx, #tag)?
+                #encode_value
             }
         )
     };
 
-    Ok(quote!(
-        #pad_before
-        #magic
-        #encode;
-        #pad_after
-    ))
+    Ok(with_field_ctx(attrs.field_ctx.as_ref(), encode))
+}
+
+fn encode_item_tag(crate_path: &TokenStream, item_tag: bool, tag: TokenStream) -> TokenStream {
+    if item_tag {
+        quote!(#crate_path::TagItems(#tag, |_| #crate_path::Untagged))
+    } else {
+        tag
+    }
+}
+
+fn encode_value(
+    crate_path: &TokenStream,
+    endianness: &TokenStream,
+    map: Option<&Map>,
+    field_ref: &TokenStream,
+    tag: &TokenStream,
+    span: Span,
+) -> Result<TokenStream> {
+    Ok(match map {
+        Some(Map::Wire { typ, encode, .. }) => {
+            let map_back = match encode {
+                Some(MapFn::Infallible(map_back)) => quote!((#map_back)(#field_ref)),
+                Some(MapFn::Fallible(try_map_back)) => quote!((#try_map_back)(#field_ref)?),
+                None => {
+                    return Err(Error::new(
+                        span,
+                        "Wire type must specify 'map_back' or 'try_map_back'",
+                    ))
+                }
+            };
+            quote!({
+                let __wire: #typ = #map_back;
+                #crate_path::BitEncode::encode::<_, #endianness>(&__wire, __io_writer, __ctx, #tag)?
+            })
+        }
+        Some(Map::With(with)) => {
+            quote!(#with::encode::<_, #endianness>(#field_ref, __io_writer, __ctx, #tag)?)
+        }
+        None => {
+            quote!(
+                #crate_path::BitEncode::encode::<_, #endianness>(
+                    #field_ref,
+                    __io_writer,
+                    __ctx,
+                    #tag
+                )?
+            )
+        }
+    })
 }
 
 fn encode_named_fields(
     parent_attrs: &Attrs,
     fields_named: &syn::FieldsNamed,
-    self_prefix: bool,
 ) -> Result<TokenStream> {
     let field_encoders = fields_named
         .named
         .iter()
         .map(|field| {
             let field_name = &field.ident;
-            encode(
-                parent_attrs,
-                field,
-                &if self_prefix {
-                    quote!(&self. #field_name)
-                } else {
-                    quote!(#field_name)
-                },
-            )
+            encode(parent_attrs, field, &quote!(#field_name))
         })
         .collect::<Result<Vec<_>>>()?;
 
@@ -246,45 +517,37 @@ fn encode_named_fields(
 fn decode_unnamed_fields(
     parent_attrs: &Attrs,
     fields_unnamed: &syn::FieldsUnnamed,
-) -> Result<TokenStream> {
-    let field_initializers = fields_unnamed
+) -> Result<(TokenStream, TokenStream)> {
+    let binding_names: Vec<_> = (0..fields_unnamed.unnamed.len())
+        .map(|i| syn::Ident::new(format!("field_{i}").as_str(), Span::call_site()))
+        .collect();
+
+    let fields = fields_unnamed
         .unnamed
         .iter()
-        .map(|field| {
-            let field_ty = &field.ty;
-            let decode = decode(parent_attrs, field)?;
-
-            Ok(quote!(
-                {
-                    let res: #field_ty = #decode;
-                    res
-                }
-            ))
+        .zip(&binding_names)
+        .enumerate()
+        .map(|(field_index, (field, binding))| {
+            decode(parent_attrs, field, binding, &field_index.to_string())
         })
         .collect::<Result<Vec<_>>>()?;
 
-    Ok(quote!( ( #( #field_initializers ),* ) ))
+    Ok((quote!( #( #fields )* ), quote!( ( #( #binding_names ),* ) )))
 }
 
 fn encode_unnamed_fields(
     parent_attrs: &Attrs,
     fields_unnamed: &syn::FieldsUnnamed,
-    self_prefix: bool,
 ) -> Result<TokenStream> {
     let field_encoders: Vec<_> = fields_unnamed
         .unnamed
         .iter()
         .enumerate()
         .map(|(field_index, field)| {
-            let field_index = syn::Index::from(field_index);
             encode(
                 parent_attrs,
                 field,
-                &if self_prefix {
-                    quote!(&self. #field_index)
-                } else {
-                    format!("field_{}", field_index.index).parse()?
-                },
+                &format!("field_{field_index}").parse()?,
             )
         })
         .collect::<Result<Vec<_>>>()?;
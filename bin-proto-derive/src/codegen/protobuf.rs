@@ -0,0 +1,173 @@
+use crate::attr::{AttrKind, Attrs, Tag};
+use proc_macro2::{Span, TokenStream};
+use syn::{spanned::Spanned, Error, Result};
+
+struct NumberedField<'a> {
+    binding: syn::Ident,
+    field: &'a syn::Field,
+    attrs: Attrs,
+}
+
+impl NumberedField<'_> {
+    fn number(&self) -> Result<&syn::Expr> {
+        self.attrs.field_number.as_ref().ok_or_else(|| {
+            Error::new(
+                self.field.span(),
+                "protobuf field missing 'field_number' attribute",
+            )
+        })
+    }
+}
+
+/// Rejects container attributes that the protobuf wire format cannot honour.
+pub fn check_container(attrs: &Attrs, span: Span) -> Result<()> {
+    check_supported(&[
+        (attrs.magic.is_some(), "magic"),
+        (attrs.pad_before.is_some(), "pad_before"),
+        (attrs.pad_after.is_some(), "pad_after"),
+        (attrs.pad_value.is_some(), "pad_value"),
+        (attrs.pad_with.is_some(), "pad_with"),
+        (attrs.strict, "strict"),
+        (attrs.bit_order.is_some(), "bit_order"),
+    ])
+    .map_err(|option| unsupported(span, option))
+}
+
+fn numbered_fields<'a>(
+    parent_attrs: &Attrs,
+    fields: &'a syn::Fields,
+) -> Result<Vec<NumberedField<'a>>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let attrs = Attrs::parse(
+                Some(parent_attrs),
+                field.attrs.as_slice(),
+                Some(AttrKind::Field),
+                field.span(),
+            )?;
+            check_supported(&[
+                (attrs.bits.is_some(), "bits"),
+                (attrs.field_ctx.is_some(), "ctx"),
+                (attrs.untagged, "untagged"),
+                (matches!(attrs.tag, Some(Tag::External(_))), "tag"),
+                (matches!(attrs.tag, Some(Tag::Prepend { .. })), "tag_type"),
+                (attrs.write_value.is_some(), "write_value"),
+                (attrs.until.is_some(), "until"),
+                (attrs.terminator.is_some(), "terminator"),
+                (attrs.item_tag.is_some(), "item_tag"),
+                (attrs.encode_tag.is_some(), "encode_tag"),
+                (attrs.ber.is_some(), "implicit or explicit"),
+                (attrs.endian_from.is_some(), "endian_from"),
+                (attrs.magic.is_some(), "magic"),
+                (attrs.pad_before.is_some(), "pad_before"),
+                (attrs.pad_after.is_some(), "pad_after"),
+                (attrs.pad_value.is_some(), "pad_value"),
+                (attrs.pad_with.is_some(), "pad_with"),
+                (attrs.strict, "strict"),
+                (attrs.assert.is_some(), "assert"),
+                (attrs.map.is_some(), "wire_type or with"),
+                (attrs.after_decode.is_some(), "after_decode"),
+                (attrs.before_encode.is_some(), "before_encode"),
+            ])
+            .map_err(|option| unsupported(field.span(), option))?;
+
+            Ok(NumberedField {
+                binding: field.ident.clone().unwrap_or_else(|| {
+                    syn::Ident::new(format!("field_{index}").as_str(), Span::call_site())
+                }),
+                field,
+                attrs,
+            })
+        })
+        .collect()
+}
+
+/// Returns the first of the options that is used.
+fn check_supported<'a>(options: &[(bool, &'a str)]) -> core::result::Result<(), &'a str> {
+    options
+        .iter()
+        .find(|(used, _)| *used)
+        .map_or(Ok(()), |(_, option)| Err(option))
+}
+
+fn unsupported(span: Span, option: &str) -> Error {
+    Error::new(
+        span,
+        format!("'{option}' cannot be used with 'format = protobuf'"),
+    )
+}
+
+pub fn decode_message(
+    parent_attrs: &Attrs,
+    fields: &syn::Fields,
+) -> Result<(TokenStream, TokenStream)> {
+    let crate_path = parent_attrs.crate_path();
+    let numbered = numbered_fields(parent_attrs, fields)?;
+
+    let bindings = numbered.iter().map(|field| {
+        let binding = &field.binding;
+        let ty = &field.field.ty;
+        let default = field.attrs.default.as_ref().map_or_else(
+            || quote!(::core::default::Default::default()),
+            |default| quote!(#default),
+        );
+        quote!(let mut #binding: #ty = #default;)
+    });
+    let merges = numbered
+        .iter()
+        .filter(|field| !field.attrs.skip_decode)
+        .map(|field| {
+            let binding = &field.binding;
+            let number = field.number()?;
+            Ok(quote!(
+                if __key.field_number == #number {
+                    #crate_path::protobuf::Field::merge_field(
+                        &mut #binding,
+                        __io_reader,
+                        __key.wire_type,
+                    )?;
+                } else
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let binding_names = numbered.iter().map(|field| &field.binding);
+    let initializers = match fields {
+        syn::Fields::Named(_) => quote!( { #( #binding_names ),* } ),
+        syn::Fields::Unnamed(_) => quote!( ( #( #binding_names ),* ) ),
+        syn::Fields::Unit => TokenStream::new(),
+    };
+
+    Ok((
+        quote!(
+            #( #bindings )*
+            while let ::core::option::Option::Some(__key) =
+                #crate_path::protobuf::decode_key(__io_reader)?
+            {
+                #( #merges )* {
+                    #crate_path::protobuf::skip_field(__io_reader, __key.wire_type)?;
+                }
+            }
+        ),
+        initializers,
+    ))
+}
+
+pub fn encode_message(parent_attrs: &Attrs, fields: &syn::Fields) -> Result<TokenStream> {
+    let crate_path = parent_attrs.crate_path();
+    let encodes = numbered_fields(parent_attrs, fields)?
+        .iter()
+        .filter(|field| !field.attrs.skip_encode)
+        .map(|field| {
+            let binding = &field.binding;
+            let number = field.number()?;
+            Ok(quote!(
+                #crate_path::protobuf::Field::encode_field(#binding, __io_writer, #number)?;
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(quote!( #( #encodes )* ))
+}
@@ -9,7 +9,10 @@ pub enum TraitImplType {
     Encode,
     TaggedDecode(syn::Type),
     UntaggedEncode,
+    SelectorEncode(syn::Type),
     Discriminable,
+    KnownDiscriminant,
+    Describe,
 }
 
 pub fn impl_trait_for(
@@ -33,6 +36,7 @@ pub fn impl_trait_for(
             | TraitImplType::Encode
             | TraitImplType::TaggedDecode(_)
             | TraitImplType::UntaggedEncode
+            | TraitImplType::SelectorEncode(_)
     ) {
         if let Some(ctx_generics) = attrs.ctx_generics {
             generics.params.extend(ctx_generics);
@@ -68,6 +72,10 @@ pub fn impl_trait_for(
             trait_generics.push(quote!(#crate_path::Untagged));
             quote!(BitEncode)
         }
+        TraitImplType::SelectorEncode(discriminant) => {
+            trait_generics.push(quote!(#crate_path::Selector<#discriminant>));
+            quote!(BitEncode)
+        }
         TraitImplType::TaggedDecode(discriminant) => {
             let mut bounds = Punctuated::new();
             bounds.push(parse_quote!(::core::convert::TryInto<#discriminant>));
@@ -85,6 +93,8 @@ pub fn impl_trait_for(
             quote!(BitDecode)
         }
         TraitImplType::Discriminable => quote!(Discriminable),
+        TraitImplType::KnownDiscriminant => quote!(KnownDiscriminant),
+        TraitImplType::Describe => quote!(dynamic::Describe),
     };
 
     let (impl_generics, _, where_clause) = generics.split_for_impl();
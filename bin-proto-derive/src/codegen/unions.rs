@@ -0,0 +1,123 @@
+use crate::attr::{AttrKind, Attrs};
+use proc_macro2::TokenStream;
+use syn::{parse_quote, spanned::Spanned, Error, Result};
+
+struct UnionField<'a> {
+    field: &'a syn::Field,
+    discriminant: syn::Pat,
+    other: bool,
+    skip_encode: bool,
+    skip_decode: bool,
+}
+
+fn union_fields<'a>(
+    parent_attrs: &Attrs,
+    fields: &'a syn::FieldsNamed,
+) -> Result<Vec<UnionField<'a>>> {
+    let mut union_fields = fields
+        .named
+        .iter()
+        .map(|field| {
+            let attrs = Attrs::parse(
+                Some(parent_attrs),
+                field.attrs.as_slice(),
+                Some(AttrKind::Variant),
+                field.span(),
+            )?;
+            let discriminant = if attrs.other {
+                parse_quote!(_)
+            } else {
+                let discriminant = attrs
+                    .discriminant
+                    .ok_or_else(|| Error::new(field.span(), "missing discriminant"))?;
+                parse_quote!(#discriminant)
+            };
+
+            Ok(UnionField {
+                field,
+                discriminant,
+                other: attrs.other,
+                skip_encode: attrs.skip_encode,
+                skip_decode: attrs.skip_decode,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    union_fields.sort_by_key(|field| field.other);
+    Ok(union_fields)
+}
+
+pub fn decode_union_fields(
+    parent_attrs: &Attrs,
+    discriminant_ty: &syn::Type,
+    fields: &syn::FieldsNamed,
+) -> Result<TokenStream> {
+    let crate_path = parent_attrs.crate_path();
+    let discriminant_match_branches = union_fields(parent_attrs, fields)?
+        .into_iter()
+        .filter(|field| !field.skip_decode)
+        .map(|UnionField {
+                 field,
+                 discriminant,
+                 ..
+             }| {
+            let field_name = &field.ident;
+            quote!(
+                #discriminant => Self {
+                    #field_name: #crate_path::BitDecode::decode::<_, __E>(__io_reader, __ctx, ())?
+                }
+            )
+        });
+
+    Ok(quote!(
+        #[allow(unreachable_patterns)]
+        match ::core::convert::TryInto::<#discriminant_ty>::try_into(__tag.0)
+            .map_err(|_| #crate_path::Error::TagConvert)? {
+            #(#discriminant_match_branches,)*
+            _ => {
+                return ::core::result::Result::Err(#crate_path::Error::Discriminant);
+            },
+        }
+    ))
+}
+
+pub fn encode_union_fields(parent_attrs: &Attrs, fields: &syn::FieldsNamed) -> Result<TokenStream> {
+    let crate_path = parent_attrs.crate_path();
+    let discriminant_match_branches = union_fields(parent_attrs, fields)?.into_iter().map(
+        |UnionField {
+             field,
+             discriminant,
+             skip_encode,
+             ..
+         }| {
+            let field_name = &field.ident;
+            if skip_encode {
+                quote!(
+                    #discriminant => {
+                        return ::core::result::Result::Err(#crate_path::Error::EncodeSkipped);
+                    }
+                )
+            } else {
+                quote!(
+                    #discriminant => #crate_path::BitEncode::encode::<_, __E>(
+                        // SAFETY: The caller of `Selector::new` guarantees that the selected
+                        // field is active.
+                        unsafe { &self.#field_name },
+                        __io_writer,
+                        __ctx,
+                        ()
+                    )?
+                )
+            }
+        },
+    );
+
+    Ok(quote!(
+        #[allow(unreachable_patterns)]
+        match __tag.into_inner() {
+            #(#discriminant_match_branches,)*
+            _ => {
+                return ::core::result::Result::Err(#crate_path::Error::Discriminant);
+            },
+        }
+    ))
+}
@@ -19,15 +19,18 @@ mod attr;
 mod codegen;
 mod enums;
 
-use attr::{AttrKind, Attrs};
+use attr::{AttrKind, Attrs, Format};
 use codegen::{
-    decode_pad, encode_pad,
+    decode_depth_guard, decode_pad, encode_pad,
     trait_impl::{impl_trait_for, TraitImplType},
 };
 use proc_macro2::TokenStream;
 use syn::{parse_macro_input, spanned::Spanned, Error, Result};
 
-use crate::codegen::enums::{decode_discriminant, encode_discriminant, variant_discriminant};
+use crate::codegen::enums::{
+    bind_fields_pattern, decode_discriminant, encode_discriminant, variant_decodable,
+    variant_discriminant,
+};
 
 #[derive(Clone, Copy)]
 enum Operation {
@@ -55,17 +58,67 @@ pub fn encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     .into()
 }
 
+#[proc_macro_derive(Describe, attributes(bin_proto))]
+pub fn describe(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast: syn::DeriveInput = parse_macro_input!(input as syn::DeriveInput);
+    match impl_describe(&ast) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+    .into()
+}
+
 fn impl_codec(ast: &syn::DeriveInput, codec_type: Operation) -> Result<TokenStream> {
     match ast.data {
         syn::Data::Struct(ref s) => impl_for_struct(ast, s, codec_type),
         syn::Data::Enum(ref e) => impl_for_enum(ast, e, codec_type),
-        syn::Data::Union(..) => Err(Error::new(
-            ast.span(),
-            "bin-proto traits are not derivable on unions",
-        )),
+        syn::Data::Union(ref u) => impl_for_union(ast, u, codec_type),
     }
 }
 
+fn impl_describe(ast: &syn::DeriveInput) -> Result<TokenStream> {
+    let schema = match ast.data {
+        syn::Data::Struct(ref s) => {
+            let attrs = Attrs::parse(
+                None,
+                ast.attrs.as_slice(),
+                Some(AttrKind::Struct),
+                ast.span(),
+            )?;
+            if attrs.format.is_some() {
+                return Err(Error::new(
+                    ast.span(),
+                    "cannot derive Describe for structs with a 'format' attribute",
+                ));
+            }
+            codegen::describe::check_supported(&attrs, ast.span())?;
+            codegen::describe::struct_schema(&attrs, &s.fields)?
+        }
+        syn::Data::Enum(ref e) => {
+            let attrs = Attrs::parse(None, ast.attrs.as_slice(), Some(AttrKind::Enum), ast.span())?;
+            if attrs.pad_before.is_some() || attrs.pad_after.is_some() {
+                return Err(Error::new(
+                    ast.span(),
+                    "cannot derive Describe for enums with padding",
+                ));
+            }
+            codegen::describe::check_supported(&attrs, ast.span())?;
+            let plan = enums::Enum::try_new(&attrs, ast, e)?;
+            codegen::describe::enum_schema(&attrs, &plan)?
+        }
+        syn::Data::Union(_) => {
+            return Err(Error::new(ast.span(), "cannot derive Describe for unions"));
+        }
+    };
+    let crate_path = Attrs::parse(None, ast.attrs.as_slice(), None, ast.span())?.crate_path();
+    let impl_body = quote!(
+        fn schema() -> #crate_path::dynamic::Schema {
+            #schema
+        }
+    );
+    impl_trait_for(ast, &impl_body, &TraitImplType::Describe)
+}
+
 fn impl_for_struct(
     ast: &syn::DeriveInput,
     strukt: &syn::DataStruct,
@@ -77,21 +130,27 @@ fn impl_for_struct(
         Some(AttrKind::Struct),
         ast.span(),
     )?;
+    if attrs.format.is_some() {
+        codegen::protobuf::check_container(&attrs, ast.span())?;
+    }
     let crate_path = attrs.crate_path();
     let ctx_ty = attrs.ctx_ty();
 
     let (impl_body, trait_type) = match codec_type {
         Operation::Decode => {
-            let (decodes, initializers) = codegen::decodes(&attrs, &strukt.fields)?;
-            let pad_before = attrs
-                .pad_before
-                .as_ref()
-                .map(|pad| decode_pad(&crate_path, pad));
-            let pad_after = attrs
-                .pad_after
-                .as_ref()
-                .map(|pad| decode_pad(&crate_path, pad));
+            let (decodes, initializers) = match attrs.format {
+                Some(Format::Protobuf) => {
+                    codegen::protobuf::decode_message(&attrs, &strukt.fields)?
+                }
+                None => codegen::decodes(&attrs, &strukt.fields)?,
+            };
+            let pad_before = attrs.pad_before.as_ref().map(|pad| decode_pad(&attrs, pad));
+            let pad_after = attrs.pad_after.as_ref().map(|pad| decode_pad(&attrs, pad));
             let magic = attrs.decode_magic();
+            let assert = attrs.decode_assert(&ast.ident.to_string());
+            let after_decode = attrs.after_decode();
+            let (bit_order_before, bit_order_after) = attrs.decode_bit_order();
+            let depth_guard = decode_depth_guard(&attrs, &ast.ident, strukt.fields.iter());
 
             (
                 quote!(
@@ -104,10 +163,15 @@ fn impl_for_struct(
                         __R: #crate_path::BitRead,
                         __E: #crate_path::Endianness,
                     {
+                        #depth_guard
+                        #bit_order_before
                         #pad_before
                         #magic
                         #decodes
                         #pad_after
+                        #bit_order_after
+                        #assert
+                        #after_decode
                         ::core::result::Result::Ok(Self #initializers)
                     }
                 ),
@@ -115,16 +179,21 @@ fn impl_for_struct(
             )
         }
         Operation::Encode => {
-            let encodes = codegen::encodes(&attrs, &strukt.fields, true)?;
-            let pad_before = attrs
-                .pad_before
-                .as_ref()
-                .map(|pad| encode_pad(&crate_path, pad));
-            let pad_after = attrs
-                .pad_after
-                .as_ref()
-                .map(|pad| encode_pad(&crate_path, pad));
+            let encodes = match attrs.format {
+                Some(Format::Protobuf) => {
+                    codegen::protobuf::encode_message(&attrs, &strukt.fields)?
+                }
+                None => codegen::encodes(&attrs, &strukt.fields)?,
+            };
+            let fields_pattern = bind_fields_pattern(
+                &syn::Ident::new("Self", proc_macro2::Span::call_site()),
+                &strukt.fields,
+            );
+            let pad_before = attrs.pad_before.as_ref().map(|pad| encode_pad(&attrs, pad));
+            let pad_after = attrs.pad_after.as_ref().map(|pad| encode_pad(&attrs, pad));
             let magic = attrs.encode_magic();
+            let before_encode = attrs.before_encode();
+            let (bit_order_before, bit_order_after) = attrs.encode_bit_order();
 
             (
                 quote!(
@@ -138,10 +207,15 @@ fn impl_for_struct(
                         __W: #crate_path::BitWrite,
                         __E: #crate_path::Endianness,
                     {
+                        #[allow(unused_variables)]
+                        let #fields_pattern = *self;
+                        #before_encode
+                        #bit_order_before
                         #pad_before
                         #magic
                         #encodes
                         #pad_after
+                        #bit_order_after
                         ::core::result::Result::Ok(())
                     }
                 ),
@@ -153,6 +227,73 @@ fn impl_for_struct(
     impl_trait_for(ast, &impl_body, &trait_type)
 }
 
+fn impl_for_union(
+    ast: &syn::DeriveInput,
+    u: &syn::DataUnion,
+    codec_type: Operation,
+) -> Result<TokenStream> {
+    let attrs = Attrs::parse(
+        None,
+        ast.attrs.as_slice(),
+        Some(AttrKind::Union),
+        ast.span(),
+    )?;
+    let crate_path = attrs.crate_path();
+    let ctx_ty = attrs.ctx_ty();
+    let discriminant_ty = attrs
+        .discriminant_type
+        .as_ref()
+        .ok_or_else(|| Error::new(ast.span(), "union missing 'discriminant_type' attribute."))?;
+
+    match codec_type {
+        Operation::Decode => {
+            let decode_field =
+                codegen::unions::decode_union_fields(&attrs, discriminant_ty, &u.fields)?;
+            let impl_body = quote!(
+                fn decode<__R, __E>(
+                    __io_reader: &mut __R,
+                    __ctx: &mut #ctx_ty,
+                    __tag: #crate_path::Tag<__Tag>,
+                ) -> #crate_path::Result<Self>
+                where
+                    __R: #crate_path::BitRead,
+                    __E: #crate_path::Endianness,
+                {
+                    ::core::result::Result::Ok(#decode_field)
+                }
+            );
+            impl_trait_for(
+                ast,
+                &impl_body,
+                &TraitImplType::TaggedDecode(discriminant_ty.clone()),
+            )
+        }
+        Operation::Encode => {
+            let encode_field = codegen::unions::encode_union_fields(&attrs, &u.fields)?;
+            let impl_body = quote!(
+                fn encode<__W, __E>(
+                    &self,
+                    __io_writer: &mut __W,
+                    __ctx: &mut #ctx_ty,
+                    __tag: #crate_path::Selector<#discriminant_ty>,
+                ) -> #crate_path::Result<()>
+                where
+                    __W: #crate_path::BitWrite,
+                    __E: #crate_path::Endianness,
+                {
+                    #encode_field;
+                    ::core::result::Result::Ok(())
+                }
+            );
+            impl_trait_for(
+                ast,
+                &impl_body,
+                &TraitImplType::SelectorEncode(discriminant_ty.clone()),
+            )
+        }
+    }
+}
+
 #[allow(clippy::too_many_lines)]
 fn impl_for_enum(
     ast: &syn::DeriveInput,
@@ -168,6 +309,12 @@ fn impl_for_enum(
     Ok(match codec_type {
         Operation::Decode => {
             let decode_variant = codegen::enums::decode_variant_fields(&plan)?;
+            let depth_guard = decode_depth_guard(
+                &attrs,
+                &ast.ident,
+                e.variants.iter().flat_map(|variant| &variant.fields),
+            );
+            let after_decode = attrs.after_decode();
             let impl_body = quote!(
                 fn decode<__R, __E>(
                     __io_reader: &mut __R,
@@ -178,7 +325,10 @@ fn impl_for_enum(
                     __R: #crate_path::BitRead,
                     __E: #crate_path::Endianness,
                 {
-                    ::core::result::Result::Ok(#decode_variant)
+                    #depth_guard
+                    let __value = #decode_variant;
+                    #after_decode
+                    ::core::result::Result::Ok(__value)
                 }
             );
             let tagged_decode_impl = impl_trait_for(
@@ -208,21 +358,29 @@ fn impl_for_enum(
             );
             let decode_impl = impl_trait_for(ast, &impl_body, &TraitImplType::Decode)?;
 
+            let variant_decodable = variant_decodable(&plan);
+            let impl_body = quote!(
+                type Discriminant = #discriminant_ty;
+
+                #[allow(clippy::match_like_matches_macro)]
+                fn is_decodable(discriminant: &Self::Discriminant) -> bool {
+                    #variant_decodable
+                }
+            );
+            let known_discriminant_impl =
+                impl_trait_for(ast, &impl_body, &TraitImplType::KnownDiscriminant)?;
+
             quote!(
                 #tagged_decode_impl
                 #decode_impl
+                #known_discriminant_impl
             )
         }
         Operation::Encode => {
             let encode_variant = codegen::enums::encode_variant_fields(&plan)?;
-            let pad_before = attrs
-                .pad_before
-                .as_ref()
-                .map(|pad| encode_pad(&crate_path, pad));
-            let pad_after = attrs
-                .pad_after
-                .as_ref()
-                .map(|pad| encode_pad(&crate_path, pad));
+            let pad_before = attrs.pad_before.as_ref().map(|pad| encode_pad(&attrs, pad));
+            let pad_after = attrs.pad_after.as_ref().map(|pad| encode_pad(&attrs, pad));
+            let before_encode = attrs.before_encode();
             let impl_body = quote!(
                 fn encode<__W, __E>(
                     &self,
@@ -234,6 +392,7 @@ fn impl_for_enum(
                     __W: #crate_path::BitWrite,
                     __E: #crate_path::Endianness,
                 {
+                    #before_encode
                     #pad_before
                     #encode_variant
                     #pad_after
@@ -266,16 +425,12 @@ fn impl_for_enum(
                     __W: #crate_path::BitWrite,
                     __E: #crate_path::Endianness,
                 {
+                    #before_encode
                     #pad_before
                     #encode_discriminant
-                    let res = <Self as #crate_path::BitEncode<_, _>>::encode::<_, __E>(
-                        self,
-                        __io_writer,
-                        __ctx,
-                        #crate_path::Untagged
-                    )?;
+                    #encode_variant
                     #pad_after
-                    ::core::result::Result::Ok(res)
+                    ::core::result::Result::Ok(())
                 }
             );
             let encode_impl = impl_trait_for(ast, &impl_body, &TraitImplType::Encode)?;
@@ -0,0 +1,832 @@
+//! ASN.1 Basic and Distinguished Encoding Rules
+//!
+//! Values are encoded as an [`Identifier`], a [`Length`], and their contents, always in network
+//! byte order, regardless of the endianness of the codec. Types implementing [`Universal`] decode
+//! their contents with a [`Tag`] of their [`Length`], and encode them with [`Untagged`], so they
+//! can be re-tagged with [`Implicit`].
+//!
+//! Values are decoded with BER and encoded with DER. Encoding always produces definite lengths in
+//! their shortest form and booleans of `0xff`, as required by DER. Decoding is more lenient, and
+//! also accepts lengths that are not in their shortest form, any non-zero byte as `true`, and
+//! indefinite lengths of constructed values, so decoding and re-encoding a value does not always
+//! reproduce its bytes.
+//!
+//! ```
+//! # use bin_proto::{BitCodec, BitDecode, BitEncode, ber::{Integer, OctetString, Sequence}};
+//! #[derive(Debug, BitDecode, BitEncode, PartialEq)]
+//! struct Record {
+//!     version: Integer,
+//!     #[bin_proto(implicit = 0)]
+//!     id: OctetString,
+//!     #[bin_proto(explicit = 1)]
+//!     count: Integer,
+//! }
+//!
+//! let record = Sequence(Record {
+//!     version: Integer(1),
+//!     id: OctetString(vec![0xab]),
+//!     count: Integer(300),
+//! });
+//! let bytes = [
+//!     0x30, 0x0c, // SEQUENCE
+//!     0x02, 0x01, 0x01, // INTEGER 1
+//!     0x80, 0x01, 0xab, // [0] IMPLICIT OCTET STRING
+//!     0xa1, 0x04, 0x02, 0x02, 0x01, 0x2c, // [1] EXPLICIT INTEGER 300
+//! ];
+//! assert_eq!(record.encode_bytes(bin_proto::BigEndian).unwrap(), bytes);
+//! assert_eq!(
+//!     Sequence::<Record>::decode_bytes(&bytes, bin_proto::BigEndian).unwrap().0,
+//!     record
+//! );
+//! ```
+
+use crate::{limits, BitDecode, BitEncode, Error, Result, Tag, Untagged};
+
+use alloc::{string::String, vec::Vec};
+use bitstream_io::{BitRead, BitReader, BitWrite, BitWriter, Endianness};
+
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(not(feature = "std"))]
+use no_std_io2::io;
+
+/// The class of an [`Identifier`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Class {
+    /// Types defined by ASN.1, such as `INTEGER` or `SEQUENCE`.
+    Universal,
+    /// Types specific to an application.
+    Application,
+    /// Types specific to their context, written `[n]`.
+    ContextSpecific,
+    /// Types specific to an enterprise.
+    Private,
+}
+
+/// The identifier octets of a value, consisting of its class, whether it's constructed, and its
+/// tag number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Identifier {
+    /// The class of the tag.
+    pub class: Class,
+    /// Whether the contents are a sequence of encoded values rather than a primitive value.
+    pub constructed: bool,
+    /// The tag number.
+    pub number: u32,
+}
+
+/// The length octets of a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Length {
+    /// The contents occupy the given number of bytes.
+    Definite(usize),
+    /// The contents are terminated by two zero bytes. Only valid for constructed values.
+    Indefinite,
+}
+
+/// An ASN.1 type with a tag in the [`Class::Universal`] class.
+pub trait Universal {
+    /// The identifier of the type when it isn't re-tagged.
+    const IDENTIFIER: Identifier;
+}
+
+/// Re-tags a [`Universal`] type as `[n] IMPLICIT`, replacing its identifier with the
+/// context-specific tag number `n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Implicit(pub u32);
+
+/// Tags a type as `[n] EXPLICIT`, wrapping its encoding in a constructed value with the
+/// context-specific tag number `n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Explicit(pub u32);
+
+/// `BOOLEAN`. Any non-zero byte decodes as `true`, and `true` is encoded as `0xff`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Boolean(pub bool);
+
+/// `INTEGER`, limited to the range of [`i64`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Integer(pub i64);
+
+/// `NULL`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Null;
+
+/// `OCTET STRING`, in its primitive encoding.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OctetString(pub Vec<u8>);
+
+/// `UTF8String`, in its primitive encoding.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Utf8String(pub String);
+
+/// `OBJECT IDENTIFIER`, as its arcs.
+///
+/// Encoding fails with [`Error::Ber`] if there are fewer than two arcs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ObjectIdentifier(pub Vec<u64>);
+
+/// `SEQUENCE`, with contents encoded as `T`.
+///
+/// Usually `T` is a derived struct with a field for each component of the sequence.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sequence<T>(pub T);
+
+const fn universal(constructed: bool, number: u32) -> Identifier {
+    Identifier {
+        class: Class::Universal,
+        constructed,
+        number,
+    }
+}
+
+impl Universal for Boolean {
+    const IDENTIFIER: Identifier = universal(false, 1);
+}
+
+impl Universal for Integer {
+    const IDENTIFIER: Identifier = universal(false, 2);
+}
+
+impl Universal for OctetString {
+    const IDENTIFIER: Identifier = universal(false, 4);
+}
+
+impl Universal for Null {
+    const IDENTIFIER: Identifier = universal(false, 5);
+}
+
+impl Universal for ObjectIdentifier {
+    const IDENTIFIER: Identifier = universal(false, 6);
+}
+
+impl Universal for Utf8String {
+    const IDENTIFIER: Identifier = universal(false, 12);
+}
+
+impl<T> Universal for Sequence<T> {
+    const IDENTIFIER: Identifier = universal(true, 16);
+}
+
+impl<Ctx> BitDecode<Ctx> for Identifier {
+    fn decode<R, E>(read: &mut R, _: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let first = read.read_to::<u8>()?;
+        let class = match first >> 6 {
+            0 => Class::Universal,
+            1 => Class::Application,
+            2 => Class::ContextSpecific,
+            _ => Class::Private,
+        };
+        let number = if first & 0x1f == 0x1f {
+            u32::try_from(decode_base128(read)?)?
+        } else {
+            u32::from(first & 0x1f)
+        };
+        Ok(Self {
+            class,
+            constructed: first & 0x20 != 0,
+            number,
+        })
+    }
+}
+
+impl<Ctx> BitEncode<Ctx> for Identifier {
+    fn encode<W, E>(&self, write: &mut W, _: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        let class = match self.class {
+            Class::Universal => 0,
+            Class::Application => 1,
+            Class::ContextSpecific => 2,
+            Class::Private => 3,
+        };
+        let first = class << 6 | u8::from(self.constructed) << 5;
+        match u8::try_from(self.number) {
+            Ok(number) if number < 0x1f => write.write::<8, u8>(first | number)?,
+            _ => {
+                write.write::<8, u8>(first | 0x1f)?;
+                encode_base128(write, self.number.into())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<Ctx> BitDecode<Ctx> for Length {
+    fn decode<R, E>(read: &mut R, _: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        match read.read_to::<u8>()? {
+            short @ 0..0x80 => Ok(Self::Definite(short.into())),
+            0x80 => Ok(Self::Indefinite),
+            0xff => Err(Error::Ber("reserved length")),
+            long => {
+                let mut len = 0usize;
+                for _ in 0..long & 0x7f {
+                    len = len
+                        .checked_mul(0x100)
+                        .ok_or(Error::Ber("length too large"))?
+                        | usize::from(read.read_to::<u8>()?);
+                }
+                Ok(Self::Definite(len))
+            }
+        }
+    }
+}
+
+impl<Ctx> BitEncode<Ctx> for Length {
+    fn encode<W, E>(&self, write: &mut W, _: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        match *self {
+            Self::Definite(len) => {
+                if let Ok(short @ 0..0x80) = u8::try_from(len) {
+                    write.write::<8, u8>(short)?;
+                } else {
+                    let bytes = len.to_be_bytes();
+                    let skip = bytes.iter().take_while(|&&b| b == 0).count();
+                    write.write::<8, u8>(0x80 | u8::try_from(bytes.len() - skip)?)?;
+                    write.write_bytes(&bytes[skip..])?;
+                }
+            }
+            Self::Indefinite => write.write::<8, u8>(0x80)?,
+        }
+        Ok(())
+    }
+}
+
+impl Implicit {
+    /// Decodes a `T` tagged as `[n] IMPLICIT`, returning [`Error::Discriminant`] if the tag
+    /// doesn't match.
+    pub fn decode<R, E, Ctx, T>(self, read: &mut R, ctx: &mut Ctx) -> Result<T>
+    where
+        R: BitRead,
+        E: Endianness,
+        T: Universal + BitDecode<Ctx, Tag<Length>>,
+    {
+        let length = decode_header::<_, E, _>(read, ctx, context_specific::<T>(self.0))?;
+        T::decode::<_, E>(read, ctx, Tag(length))
+    }
+
+    /// Encodes `value` tagged as `[n] IMPLICIT`.
+    pub fn encode<W, E, Ctx, T>(self, value: &T, write: &mut W, ctx: &mut Ctx) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+        T: Universal + BitEncode<Ctx, Untagged>,
+    {
+        let contents = encode_contents::<E, _, _>(ctx, |write, ctx| {
+            value.encode::<_, E>(write, ctx, Untagged)
+        })?;
+        encode_value::<_, E, _>(write, ctx, context_specific::<T>(self.0), &contents)
+    }
+}
+
+impl Explicit {
+    /// Decodes a `T` tagged as `[n] EXPLICIT`, returning [`Error::Discriminant`] if the tag
+    /// doesn't match.
+    pub fn decode<R, E, Ctx, T>(self, read: &mut R, ctx: &mut Ctx) -> Result<T>
+    where
+        R: BitRead,
+        E: Endianness,
+        T: BitDecode<Ctx>,
+    {
+        let length = decode_header::<_, E, _>(read, ctx, explicit(self.0))?;
+        decode_constructed::<_, E, _, _>(read, ctx, length)
+    }
+
+    /// Encodes `value` tagged as `[n] EXPLICIT`.
+    pub fn encode<W, E, Ctx, T>(self, value: &T, write: &mut W, ctx: &mut Ctx) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+        T: BitEncode<Ctx>,
+    {
+        let contents =
+            encode_contents::<E, _, _>(ctx, |write, ctx| value.encode::<_, E>(write, ctx, ()))?;
+        encode_value::<_, E, _>(write, ctx, explicit(self.0), &contents)
+    }
+}
+
+macro_rules! impl_universal {
+    ($ty:ty $(, $generic:ident)?) => {
+        impl<Ctx $(, $generic: BitDecode<Ctx>)?> BitDecode<Ctx> for $ty {
+            fn decode<R, E>(read: &mut R, ctx: &mut Ctx, (): ()) -> Result<Self>
+            where
+                R: BitRead,
+                E: Endianness,
+            {
+                let length = decode_header::<_, E, _>(read, ctx, Self::IDENTIFIER)?;
+                Self::decode::<_, E>(read, ctx, Tag(length))
+            }
+        }
+
+        impl<Ctx $(, $generic: BitEncode<Ctx>)?> BitEncode<Ctx> for $ty {
+            fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, (): ()) -> Result<()>
+            where
+                W: BitWrite,
+                E: Endianness,
+            {
+                let contents = encode_contents::<E, _, _>(ctx, |write, ctx| {
+                    self.encode::<_, E>(write, ctx, Untagged)
+                })?;
+                encode_value::<_, E, _>(write, ctx, Self::IDENTIFIER, &contents)
+            }
+        }
+    };
+}
+
+impl_universal!(Boolean);
+impl_universal!(Integer);
+impl_universal!(Null);
+impl_universal!(OctetString);
+impl_universal!(Utf8String);
+impl_universal!(ObjectIdentifier);
+impl_universal!(Sequence<T>, T);
+
+impl<Ctx> BitDecode<Ctx, Tag<Length>> for Boolean {
+    fn decode<R, E>(read: &mut R, _: &mut Ctx, tag: Tag<Length>) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        match primitive(read, tag.0)?.as_slice() {
+            [value] => Ok(Self(*value != 0)),
+            _ => Err(Error::Ber("boolean must be one byte")),
+        }
+    }
+}
+
+impl<Ctx> BitEncode<Ctx, Untagged> for Boolean {
+    fn encode<W, E>(&self, write: &mut W, _: &mut Ctx, _: Untagged) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        write.write::<8, u8>(if self.0 { 0xff } else { 0 })?;
+        Ok(())
+    }
+}
+
+impl<Ctx> BitDecode<Ctx, Tag<Length>> for Integer {
+    fn decode<R, E>(read: &mut R, _: &mut Ctx, tag: Tag<Length>) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let bytes = primitive(read, tag.0)?;
+        let Some(first) = bytes.first() else {
+            return Err(Error::Ber("integer must not be empty"));
+        };
+        if bytes.len() > size_of::<i64>() {
+            return Err(Error::Length {
+                max: size_of::<i64>(),
+                actual: bytes.len(),
+            });
+        }
+        let sign = if first & 0x80 == 0 { 0 } else { -1 };
+        Ok(Self(
+            bytes
+                .iter()
+                .fold(sign, |value, &byte| value << 8 | i64::from(byte)),
+        ))
+    }
+}
+
+impl<Ctx> BitEncode<Ctx, Untagged> for Integer {
+    fn encode<W, E>(&self, write: &mut W, _: &mut Ctx, _: Untagged) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        let bytes = self.0.to_be_bytes();
+        let redundant = bytes
+            .windows(2)
+            .take_while(|pair| matches!(pair, [0, 0..=0x7f] | [0xff, 0x80..=0xff]))
+            .count();
+        write.write_bytes(&bytes[redundant..])?;
+        Ok(())
+    }
+}
+
+impl<Ctx> BitDecode<Ctx, Tag<Length>> for Null {
+    fn decode<R, E>(read: &mut R, _: &mut Ctx, tag: Tag<Length>) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        if primitive(read, tag.0)?.is_empty() {
+            Ok(Self)
+        } else {
+            Err(Error::Ber("null must be empty"))
+        }
+    }
+}
+
+impl<Ctx> BitEncode<Ctx, Untagged> for Null {
+    fn encode<W, E>(&self, _: &mut W, _: &mut Ctx, _: Untagged) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        Ok(())
+    }
+}
+
+impl<Ctx> BitDecode<Ctx, Tag<Length>> for OctetString {
+    fn decode<R, E>(read: &mut R, _: &mut Ctx, tag: Tag<Length>) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        Ok(Self(primitive(read, tag.0)?))
+    }
+}
+
+impl<Ctx> BitEncode<Ctx, Untagged> for OctetString {
+    fn encode<W, E>(&self, write: &mut W, _: &mut Ctx, _: Untagged) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        write.write_bytes(&self.0)?;
+        Ok(())
+    }
+}
+
+impl<Ctx> BitDecode<Ctx, Tag<Length>> for Utf8String {
+    fn decode<R, E>(read: &mut R, _: &mut Ctx, tag: Tag<Length>) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        Ok(Self(String::from_utf8(primitive(read, tag.0)?)?))
+    }
+}
+
+impl<Ctx> BitEncode<Ctx, Untagged> for Utf8String {
+    fn encode<W, E>(&self, write: &mut W, _: &mut Ctx, _: Untagged) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        write.write_bytes(self.0.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl<Ctx> BitDecode<Ctx, Tag<Length>> for ObjectIdentifier {
+    fn decode<R, E>(read: &mut R, _: &mut Ctx, tag: Tag<Length>) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let bytes = primitive(read, tag.0)?;
+        let mut reader = BitReader::<_, E>::new(io::Cursor::new(bytes.as_slice()));
+        let first = decode_base128(&mut reader)?;
+        let mut arcs = if first < 80 {
+            Vec::from([first / 40, first % 40])
+        } else {
+            Vec::from([2, first - 80])
+        };
+        while reader.position_in_bits()? < u64::try_from(bytes.len())? * 8 {
+            arcs.push(decode_base128(&mut reader)?);
+        }
+        Ok(Self(arcs))
+    }
+}
+
+impl<Ctx> BitEncode<Ctx, Untagged> for ObjectIdentifier {
+    fn encode<W, E>(&self, write: &mut W, _: &mut Ctx, _: Untagged) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        let [first, second, rest @ ..] = self.0.as_slice() else {
+            return Err(Error::Ber("object identifier must have at least two arcs"));
+        };
+        if *first > 2 || (*first < 2 && *second >= 40) {
+            return Err(Error::Ber("invalid first arcs of object identifier"));
+        }
+        encode_base128(
+            write,
+            (first * 40)
+                .checked_add(*second)
+                .ok_or(Error::Ber("invalid first arcs of object identifier"))?,
+        )?;
+        for arc in rest {
+            encode_base128(write, *arc)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Ctx, T> BitDecode<Ctx, Tag<Length>> for Sequence<T>
+where
+    T: BitDecode<Ctx>,
+{
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, tag: Tag<Length>) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        decode_constructed::<_, E, _, _>(read, ctx, tag.0).map(Self)
+    }
+}
+
+impl<Ctx, T> BitEncode<Ctx, Untagged> for Sequence<T>
+where
+    T: BitEncode<Ctx>,
+{
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, _: Untagged) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        self.0.encode::<_, E>(write, ctx, ())
+    }
+}
+
+const fn context_specific<T: Universal>(number: u32) -> Identifier {
+    Identifier {
+        class: Class::ContextSpecific,
+        constructed: T::IDENTIFIER.constructed,
+        number,
+    }
+}
+
+const fn explicit(number: u32) -> Identifier {
+    Identifier {
+        class: Class::ContextSpecific,
+        constructed: true,
+        number,
+    }
+}
+
+/// Decodes the identifier and length of a value, returning [`Error::Discriminant`] if the
+/// identifier isn't `expected`.
+fn decode_header<R, E, Ctx>(read: &mut R, ctx: &mut Ctx, expected: Identifier) -> Result<Length>
+where
+    R: BitRead,
+    E: Endianness,
+{
+    if Identifier::decode::<_, E>(read, ctx, ())? != expected {
+        return Err(Error::Discriminant);
+    }
+    let length = Length::decode::<_, E>(read, ctx, ())?;
+    if length == Length::Indefinite && !expected.constructed {
+        return Err(Error::Ber("indefinite length of primitive value"));
+    }
+    Ok(length)
+}
+
+fn decode_constructed<R, E, Ctx, T>(read: &mut R, ctx: &mut Ctx, length: Length) -> Result<T>
+where
+    R: BitRead,
+    E: Endianness,
+    T: BitDecode<Ctx>,
+{
+    match length {
+        Length::Definite(len) => {
+            limits::reserve::<u8>(0, len)?;
+            let contents = read.read_to_vec(len)?;
+            let mut reader = BitReader::<_, E>::new(io::Cursor::new(contents.as_slice()));
+            let value = T::decode::<_, E>(&mut reader, ctx, ())?;
+            let read_bits = reader.position_in_bits()?;
+            let available_bits = u64::try_from(len)? * 8;
+            if read_bits == available_bits {
+                Ok(value)
+            } else {
+                Err(Error::Underrun {
+                    read_bits,
+                    available_bits,
+                })
+            }
+        }
+        Length::Indefinite => {
+            let value = T::decode::<_, E>(read, ctx, ())?;
+            if read.read_to::<[u8; 2]>()? == [0, 0] {
+                Ok(value)
+            } else {
+                Err(Error::Ber("missing end-of-contents"))
+            }
+        }
+    }
+}
+
+fn primitive<R: BitRead>(read: &mut R, length: Length) -> Result<Vec<u8>> {
+    match length {
+        Length::Definite(len) => {
+            limits::reserve::<u8>(0, len)?;
+            Ok(read.read_to_vec(len)?)
+        }
+        Length::Indefinite => Err(Error::Ber("indefinite length of primitive value")),
+    }
+}
+
+fn encode_contents<E, Ctx, F>(ctx: &mut Ctx, f: F) -> Result<Vec<u8>>
+where
+    E: Endianness,
+    F: FnOnce(&mut BitWriter<&mut Vec<u8>, E>, &mut Ctx) -> Result<()>,
+{
+    let mut contents = Vec::new();
+    let mut writer = BitWriter::<_, E>::new(&mut contents);
+    f(&mut writer, ctx)?;
+    writer.byte_align()?;
+    Ok(contents)
+}
+
+fn encode_value<W, E, Ctx>(
+    write: &mut W,
+    ctx: &mut Ctx,
+    identifier: Identifier,
+    contents: &[u8],
+) -> Result<()>
+where
+    W: BitWrite,
+    E: Endianness,
+{
+    identifier.encode::<_, E>(write, ctx, ())?;
+    Length::Definite(contents.len()).encode::<_, E>(write, ctx, ())?;
+    write.write_bytes(contents)?;
+    Ok(())
+}
+
+fn decode_base128<R: BitRead>(read: &mut R) -> Result<u64> {
+    let mut value = 0u64;
+    loop {
+        let byte = read.read_to::<u8>()?;
+        if value == 0 && byte == 0x80 {
+            return Err(Error::Ber("non-minimal base-128 integer"));
+        }
+        if value.leading_zeros() < 7 {
+            return Err(Error::Ber("base-128 integer too large"));
+        }
+        value = value << 7 | u64::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+}
+
+fn encode_base128<W: BitWrite>(write: &mut W, value: u64) -> Result<()> {
+    let groups = (u64::BITS - value.leading_zeros()).div_ceil(7).max(1);
+    for group in (0..groups).rev() {
+        let continuation = if group == 0 { 0 } else { 0x80 };
+        #[allow(clippy::cast_possible_truncation)]
+        write.write::<8, u8>((value >> (group * 7)) as u8 & 0x7f | continuation)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod identifier {
+        use super::*;
+
+        test_codec!(
+            Identifier;
+            Identifier { class: Class::ContextSpecific, constructed: true, number: 3 } => [0xa3]
+        );
+
+        mod high_tag_number {
+            use super::*;
+
+            test_codec!(
+                Identifier;
+                Identifier { class: Class::Application, constructed: false, number: 201 }
+                    => [0x5f, 0x81, 0x49]
+            );
+        }
+    }
+
+    mod length {
+        use super::*;
+
+        test_codec!(Length; Length::Definite(0x7f) => [0x7f]);
+
+        mod long {
+            use super::*;
+
+            test_codec!(Length; Length::Definite(0x1234) => [0x82, 0x12, 0x34]);
+        }
+
+        mod indefinite {
+            use super::*;
+
+            test_codec!(Length; Length::Indefinite => [0x80]);
+        }
+    }
+
+    mod boolean {
+        use super::*;
+
+        test_codec!(Boolean; Boolean(true) => [0x01, 0x01, 0xff]);
+    }
+
+    mod integer {
+        use super::*;
+
+        test_codec!(Integer; Integer(-129) => [0x02, 0x02, 0xff, 0x7f]);
+
+        mod zero {
+            use super::*;
+
+            test_codec!(Integer; Integer(0) => [0x02, 0x01, 0x00]);
+        }
+
+        mod positive {
+            use super::*;
+
+            test_codec!(Integer; Integer(128) => [0x02, 0x02, 0x00, 0x80]);
+        }
+    }
+
+    mod null {
+        use super::*;
+
+        test_codec!(Null; Null => [0x05, 0x00]);
+    }
+
+    mod utf8_string {
+        use super::*;
+
+        test_codec!(Utf8String; Utf8String("hi".into()) => [0x0c, 0x02, b'h', b'i']);
+    }
+
+    mod object_identifier {
+        use super::*;
+
+        test_codec!(
+            ObjectIdentifier;
+            ObjectIdentifier(Vec::from([1, 2, 840, 113_549]))
+                => [0x06, 0x06, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d]
+        );
+    }
+
+    mod implicit {
+        use super::*;
+
+        #[test]
+        fn mismatched_identifier() {
+            assert!(matches!(
+                Implicit(0).decode::<_, bitstream_io::BigEndian, _, OctetString>(
+                    &mut bitstream_io::BitReader::endian(
+                        [0x81, 0x00].as_slice(),
+                        bitstream_io::BigEndian
+                    ),
+                    &mut (),
+                ),
+                Err(Error::Discriminant)
+            ));
+        }
+    }
+
+    mod indefinite_sequence {
+        use super::*;
+
+        #[test]
+        fn decode() {
+            assert_eq!(
+                Sequence::<(Integer, Null)>::decode::<_, bitstream_io::BigEndian>(
+                    &mut bitstream_io::BitReader::endian(
+                        [0x30, 0x80, 0x02, 0x01, 0x05, 0x05, 0x00, 0x00, 0x00].as_slice(),
+                        bitstream_io::BigEndian
+                    ),
+                    &mut (),
+                    (),
+                )
+                .unwrap(),
+                Sequence((Integer(5), Null))
+            );
+        }
+
+        #[test]
+        fn primitive() {
+            assert!(matches!(
+                OctetString::decode::<_, bitstream_io::BigEndian>(
+                    &mut bitstream_io::BitReader::endian(
+                        [0x04, 0x80, 0x00, 0x00].as_slice(),
+                        bitstream_io::BigEndian
+                    ),
+                    &mut (),
+                    (),
+                ),
+                Err(Error::Ber(_))
+            ));
+        }
+    }
+}
@@ -8,3 +8,13 @@ pub trait Discriminable {
     /// Returns [`None`] if the variant cannot be encoded.
     fn discriminant(&self) -> Option<Self::Discriminant>;
 }
+
+/// A trait for types decoded from a discriminant, which can tell which discriminants are known.
+/// Automatically derived for `enum`s deriving [`BitDecode`](crate::BitDecode).
+pub trait KnownDiscriminant {
+    /// The type of the discriminant.
+    type Discriminant;
+
+    /// Returns whether a variant with the discriminant can be decoded.
+    fn is_decodable(discriminant: &Self::Discriminant) -> bool;
+}
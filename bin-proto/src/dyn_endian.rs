@@ -0,0 +1,87 @@
+use crate::{BitDecode, BitEncode, Result};
+
+use bitstream_io::{BigEndian, BitRead, BitWrite, LittleEndian};
+
+/// An endianness selected at runtime, such as from a byte-order mark in a header.
+///
+/// Dispatches to the [`BigEndian`] or [`LittleEndian`] implementation of a codec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DynEndian {
+    /// Most significant byte first.
+    Big,
+    /// Least significant byte first.
+    Little,
+}
+
+impl DynEndian {
+    /// [`BitDecode`]s a value with the selected endianness.
+    pub fn decode<T, R, Ctx, Tag>(self, read: &mut R, ctx: &mut Ctx, tag: Tag) -> Result<T>
+    where
+        T: BitDecode<Ctx, Tag>,
+        R: BitRead,
+    {
+        match self {
+            Self::Big => T::decode::<_, BigEndian>(read, ctx, tag),
+            Self::Little => T::decode::<_, LittleEndian>(read, ctx, tag),
+        }
+    }
+
+    /// [`BitEncode`]s a value with the selected endianness.
+    pub fn encode<T, W, Ctx, Tag>(
+        self,
+        value: &T,
+        write: &mut W,
+        ctx: &mut Ctx,
+        tag: Tag,
+    ) -> Result<()>
+    where
+        T: BitEncode<Ctx, Tag> + ?Sized,
+        W: BitWrite,
+    {
+        match self {
+            Self::Big => value.encode::<_, BigEndian>(write, ctx, tag),
+            Self::Little => value.encode::<_, LittleEndian>(write, ctx, tag),
+        }
+    }
+}
+
+impl<T> From<&T> for DynEndian
+where
+    T: Copy + Into<Self>,
+{
+    fn from(value: &T) -> Self {
+        (*value).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bitstream_io::BitReader;
+
+    #[test]
+    fn decode_dispatch() {
+        let bytes = [0x12, 0x34];
+        assert_eq!(
+            DynEndian::Big
+                .decode::<u16, _, _, _>(
+                    &mut BitReader::endian(bytes.as_slice(), BigEndian),
+                    &mut (),
+                    ()
+                )
+                .unwrap(),
+            0x1234
+        );
+        assert_eq!(
+            DynEndian::Little
+                .decode::<u16, _, _, _>(
+                    &mut BitReader::endian(bytes.as_slice(), BigEndian),
+                    &mut (),
+                    ()
+                )
+                .unwrap(),
+            0x3412
+        );
+    }
+}
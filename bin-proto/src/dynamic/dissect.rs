@@ -0,0 +1,297 @@
+use super::{Schema, Tracer, Value};
+use crate::Error;
+
+use alloc::{string::String, vec, vec::Vec};
+use bitstream_io::{BitReader, Endianness};
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(not(feature = "std"))]
+use no_std_io2::io;
+
+const COLORS: [u8; 6] = [31, 32, 33, 34, 35, 36];
+
+/// A field of a struct or enum variant, or an item of an array, recorded by
+/// [`Schema::dissect_bytes`].
+///
+/// The discriminant of an enum, and the magic and padding of a field,
+/// are recorded as fields named `(discriminant)`, `(magic)` and `(padding)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dissected {
+    /// The names of the fields containing this field, followed by the name of this field.
+    pub path: Vec<String>,
+    /// The offset of the value of the field, in bits.
+    pub start: u64,
+    /// The length of the value of the field, in bits.
+    pub bits: u64,
+    /// The decoded value, or [`None`] if decoding failed within the field.
+    pub value: Option<Value>,
+}
+
+/// The fields recorded while decoding bytes, with the error that ended decoding, if any.
+#[derive(Debug)]
+pub struct Dissection {
+    /// The decoded bytes.
+    pub bytes: Vec<u8>,
+    /// The fields, in the order in which decoding started, with containing fields first.
+    pub fields: Vec<Dissected>,
+    /// The offset in bits at which decoding failed, and the error.
+    pub error: Option<(u64, Error)>,
+}
+
+/// An annotated tree of a [`Dissection`], one line per field, created by [`Dissection::tree`].
+pub struct Tree<'a> {
+    dissection: &'a Dissection,
+    color: bool,
+}
+
+/// A hexdump of a [`Dissection`], created by [`Dissection::hexdump`].
+pub struct Hexdump<'a> {
+    dissection: &'a Dissection,
+    color: bool,
+}
+
+#[derive(Default)]
+struct Trace {
+    fields: Vec<Dissected>,
+    open: Vec<usize>,
+}
+
+impl<E: Endianness> Tracer<BitReader<io::Cursor<&[u8]>, E>> for Trace {
+    fn enter(&mut self, read: &mut BitReader<io::Cursor<&[u8]>, E>, name: &str) {
+        let mut path = self
+            .open
+            .last()
+            .map(|&parent| self.fields[parent].path.clone())
+            .unwrap_or_default();
+        path.push(name.into());
+        self.open.push(self.fields.len());
+        self.fields.push(Dissected {
+            path,
+            start: read.position_in_bits().unwrap_or_default(),
+            bits: 0,
+            value: None,
+        });
+    }
+
+    fn exit(&mut self, read: &mut BitReader<io::Cursor<&[u8]>, E>, value: &Value) {
+        if let Some(field) = self.open.pop().map(|index| &mut self.fields[index]) {
+            field.bits = read.position_in_bits().unwrap_or_default() - field.start;
+            field.value = Some(value.clone());
+        }
+    }
+
+    fn mark(&self) -> usize {
+        self.fields.len()
+    }
+
+    fn rewind(&mut self, mark: usize) {
+        self.fields.truncate(mark);
+        self.open.retain(|&index| index < mark);
+    }
+}
+
+impl Schema {
+    /// Decodes bytes with this layout, recording the offset, length and value of every field of
+    /// a struct or enum variant, and of every item of an array, named by its index.
+    ///
+    /// Only the decoding of this schema is recorded, rather than that of any type it describes.
+    ///
+    /// ```
+    /// # use bin_proto::dynamic::{Field, Length, Schema, Value};
+    /// let schema = Schema::Struct(vec![
+    ///     Field::new("length", Schema::U8),
+    ///     Field::new("data", Schema::Bytes(Length::Field("length".into()))),
+    /// ]);
+    ///
+    /// let dissection = schema.dissect_bytes(&[2, 0xaa, 0xbb], bin_proto::BigEndian);
+    /// assert_eq!(dissection.fields[0].value, Some(Value::UInt(2)));
+    /// assert_eq!(
+    ///     dissection.tree(false).to_string(),
+    ///     r"     0.0     8  length: 2 (0x2)
+    ///      1.0    16  data: [aa bb]
+    /// "
+    /// );
+    ///
+    /// let dissection = schema.dissect_bytes(&[3, 0xaa, 0xbb], bin_proto::BigEndian);
+    /// assert_eq!(dissection.fields[1].value, None);
+    /// assert!(dissection.error.is_some());
+    /// ```
+    pub fn dissect_bytes<E>(&self, bytes: &[u8], byte_order: E) -> Dissection
+    where
+        E: Endianness,
+    {
+        let mut buffer = BitReader::endian(io::Cursor::new(bytes), byte_order);
+        let mut trace = Trace::default();
+        let result = self.decode_in::<_, E, _>(&mut buffer, &[], &mut trace);
+        let position = buffer.position_in_bits().unwrap_or_default();
+        for index in trace.open {
+            let field = &mut trace.fields[index];
+            field.bits = position - field.start;
+        }
+        Dissection {
+            bytes: bytes.into(),
+            fields: trace.fields,
+            error: result.err().map(|error| (position, error)),
+        }
+    }
+}
+
+impl Dissection {
+    /// An annotated tree of the fields, with the byte and bit offset and the length in bits of
+    /// each. Fields without nested fields are colored to match the [`Dissection::hexdump`] if
+    /// `color` is set.
+    #[must_use]
+    pub const fn tree(&self, color: bool) -> Tree<'_> {
+        Tree {
+            dissection: self,
+            color,
+        }
+    }
+
+    /// A hexdump of the bytes, 16 per line. The bytes of fields without nested fields are
+    /// colored to match the [`Dissection::tree`] if `color` is set.
+    #[must_use]
+    pub const fn hexdump(&self, color: bool) -> Hexdump<'_> {
+        Hexdump {
+            dissection: self,
+            color,
+        }
+    }
+
+    /// The color of each field, assigned in turn to the fields without nested fields.
+    fn colors(&self) -> Vec<Option<u8>> {
+        let mut leaves = 0;
+        self.fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                self.fields
+                    .get(index + 1)
+                    .is_none_or(|next| next.path.len() <= field.path.len())
+                    .then(|| {
+                        leaves += 1;
+                        COLORS[(leaves - 1) % COLORS.len()]
+                    })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Tree<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let colors = if self.color {
+            self.dissection.colors()
+        } else {
+            vec![None; self.dissection.fields.len()]
+        };
+        for (field, color) in self.dissection.fields.iter().zip(colors) {
+            if let Some(color) = color {
+                write!(f, "\x1b[{color}m")?;
+            }
+            write!(
+                f,
+                "{:>6}.{} {:>5}  {:indent$}{}",
+                field.start / 8,
+                field.start % 8,
+                field.bits,
+                "",
+                field.path.last().map_or("", String::as_str),
+                indent = 2 * (field.path.len() - 1),
+            )?;
+            match &field.value {
+                None => write!(f, ": <incomplete>")?,
+                Some(Value::Struct(_)) => {}
+                Some(value) => write!(f, ": {}", DisplayValue(value))?,
+            }
+            if color.is_some() {
+                write!(f, "\x1b[0m")?;
+            }
+            writeln!(f)?;
+        }
+        if let Some((position, error)) = &self.dissection.error {
+            writeln!(
+                f,
+                "{:>6}.{}        error: {error}",
+                position / 8,
+                position % 8
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Hexdump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Leaves are in decoding order, so the leaves of each byte follow those of the previous.
+        let colors = if self.color {
+            self.dissection.colors()
+        } else {
+            Vec::new()
+        };
+        let mut leaves = self
+            .dissection
+            .fields
+            .iter()
+            .zip(colors)
+            .filter_map(|(field, color)| Some((field, color?)))
+            .peekable();
+        for (line, bytes) in self.dissection.bytes.chunks(16).enumerate() {
+            write!(f, "{:08x} ", line * 16)?;
+            for (index, byte) in bytes.iter().enumerate() {
+                let start = u64::try_from(line * 16 + index).unwrap_or(u64::MAX) * 8;
+                while leaves
+                    .next_if(|(leaf, _)| leaf.start + leaf.bits <= start)
+                    .is_some()
+                {}
+                let color = leaves
+                    .peek()
+                    .filter(|(leaf, _)| leaf.start < start + 8)
+                    .map(|&(_, color)| color);
+                write!(f, "{}", if index == 8 { "  " } else { " " })?;
+                match color {
+                    Some(color) => write!(f, "\x1b[{color}m{byte:02x}\x1b[0m")?,
+                    None => write!(f, "{byte:02x}")?,
+                }
+            }
+            let padding = 3 * (16 - bytes.len()) + usize::from(bytes.len() <= 8);
+            write!(f, "{:padding$}  |", "")?;
+            for byte in bytes {
+                let ascii = if byte.is_ascii_graphic() || *byte == b' ' {
+                    char::from(*byte)
+                } else {
+                    '.'
+                };
+                write!(f, "{ascii}")?;
+            }
+            writeln!(f, "|")?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats a [`Value`] on a single line, summarizing nested values.
+struct DisplayValue<'a>(&'a Value);
+
+impl fmt::Display for DisplayValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Value::Bool(value) => write!(f, "{value}"),
+            Value::UInt(value) => write!(f, "{value} ({value:#x})"),
+            Value::Int(value) => write!(f, "{value}"),
+            Value::Bytes(bytes) => {
+                write!(f, "[")?;
+                for (index, byte) in bytes.iter().enumerate() {
+                    write!(f, "{}{byte:02x}", if index == 0 { "" } else { " " })?;
+                }
+                write!(f, "]")
+            }
+            Value::String(value) => write!(f, "{value:?}"),
+            Value::Array(items) => write!(f, "[{} items]", items.len()),
+            Value::Struct(fields) => write!(f, "{{{} fields}}", fields.len()),
+            Value::Variant { name, .. } => write!(f, "{name}"),
+        }
+    }
+}
@@ -0,0 +1,952 @@
+//! Decoding and encoding of values whose layout is only known at runtime
+//!
+//! A [`Schema`] describes a layout with the same semantics as the corresponding types and derive
+//! attributes, and decodes into, or encodes from, a [`Value`] tree.
+//!
+//! ```
+//! # use bin_proto::dynamic::{Field, Length, Schema, Value};
+//! let schema = Schema::Struct(vec![
+//!     Field {
+//!         magic: b"HI".to_vec(),
+//!         ..Field::new("version", Schema::Bits { width: 4, signed: false })
+//!     },
+//!     Field::new("flags", Schema::Bits { width: 4, signed: false }),
+//!     Field::new("name", Schema::String(Length::Prefix(Box::new(Schema::U8)))),
+//! ]);
+//!
+//! let bytes = [b'H', b'I', 0x21, 0x02, b'o', b'k'];
+//! let value = Value::Struct(vec![
+//!     ("version".into(), Value::UInt(2)),
+//!     ("flags".into(), Value::UInt(1)),
+//!     ("name".into(), Value::String("ok".into())),
+//! ]);
+//! assert_eq!(schema.decode_bytes(&bytes, bin_proto::BigEndian).unwrap(), (value.clone(), 48));
+//! assert_eq!(schema.encode_bytes(&value, bin_proto::BigEndian).unwrap(), bytes);
+//! ```
+//!
+//! The schema of a type deriving [`BitDecode`] and [`BitEncode`] can be exported by also deriving
+//! [`Describe`], which accepts the same attributes. Container-level magic and padding are folded
+//! into the first and last fields where possible. Enums with padding, unions, and structs with a
+//! `format` attribute can't be described.
+//!
+//! [`Schema::dissect_bytes`] records where every field was decoded from, and renders an annotated
+//! tree and hexdump, which helps when diagnosing a failed decode. Only decoding with a [`Schema`]
+//! is recorded, so types deriving [`BitDecode`] are dissected through the schema from
+//! [`Describe`].
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! # use bin_proto::{dynamic::{Field, Schema}, BitDecode, BitEncode, Describe};
+//! #[derive(BitDecode, BitEncode, Describe)]
+//! #[bin_proto(magic = b"HI")]
+//! struct Header {
+//!     #[bin_proto(bits = 4)]
+//!     version: u8,
+//!     #[bin_proto(bits = 4)]
+//!     flags: u8,
+//! }
+//!
+//! let bits = Schema::Bits { width: 4, signed: false };
+//! assert_eq!(
+//!     Header::schema(),
+//!     Schema::Struct(vec![
+//!         Field { magic: b"HI".to_vec(), ..Field::new("version", bits.clone()) },
+//!         Field::new("flags", bits),
+//!     ])
+//! );
+//! # }
+//! ```
+
+mod dissect;
+
+pub use self::dissect::{Dissected, Dissection, Hexdump, Tree};
+
+use crate::{limits, BitDecode, BitEncode, Error, Result};
+
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bitstream_io::{BitRead, BitReader, BitWrite, BitWriter, Endianness};
+
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(not(feature = "std"))]
+use no_std_io2::io;
+
+/// A decoded value.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Value {
+    /// A [`Schema::Bool`].
+    Bool(bool),
+    /// An unsigned integer or bitfield.
+    UInt(u64),
+    /// A signed integer or bitfield.
+    Int(i64),
+    /// A [`Schema::Bytes`].
+    Bytes(Vec<u8>),
+    /// A [`Schema::String`].
+    String(String),
+    /// A [`Schema::Array`].
+    Array(Vec<Self>),
+    /// A [`Schema::Struct`], as its field names and values.
+    Struct(Vec<(String, Self)>),
+    /// A [`Schema::Enum`], as the name of its variant and its fields.
+    Variant {
+        /// The name of the variant.
+        name: String,
+        /// The field names and values of the variant.
+        fields: Vec<(String, Self)>,
+    },
+}
+
+/// The layout of a [`Value`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Schema {
+    /// A [`bool`].
+    Bool,
+    /// A [`u8`].
+    U8,
+    /// A [`u16`], in the endianness of the codec.
+    U16,
+    /// A [`u32`], in the endianness of the codec.
+    U32,
+    /// A [`u64`], in the endianness of the codec.
+    U64,
+    /// An [`i8`].
+    I8,
+    /// An [`i16`], in the endianness of the codec.
+    I16,
+    /// An [`i32`], in the endianness of the codec.
+    I32,
+    /// An [`i64`], in the endianness of the codec.
+    I64,
+    /// An integer with the `bits` attribute.
+    Bits {
+        /// The number of bits.
+        width: u32,
+        /// Whether the value is a [`Value::Int`] rather than a [`Value::UInt`].
+        signed: bool,
+    },
+    /// A byte vector.
+    Bytes(Length),
+    /// A UTF-8 string.
+    String(Length),
+    /// A vector of items.
+    Array {
+        /// The layout of each item.
+        item: Box<Self>,
+        /// The number of items.
+        length: Length,
+    },
+    /// A struct.
+    Struct(Vec<Field>),
+    /// An enum, with a discriminant followed by the fields of the variant it selects.
+    Enum {
+        /// The layout of the discriminant, corresponding to the `discriminant_type` and `bits`
+        /// attributes.
+        discriminant: Box<Self>,
+        /// The variants of the enum.
+        variants: Vec<Variant>,
+    },
+}
+
+/// The length of a [`Schema::Bytes`], [`Schema::String`] or [`Schema::Array`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Length {
+    /// A fixed number of items, as in an array.
+    Fixed(usize),
+    /// The value of a previous field of the same struct or variant, corresponding to the `tag`
+    /// attribute. The field isn't updated when encoding.
+    Field(String),
+    /// A length prefix, corresponding to the `tag_type` attribute.
+    Prefix(Box<Schema>),
+    /// Items until the end of the stream, corresponding to the `untagged` attribute.
+    Untagged,
+}
+
+/// A field of a [`Schema::Struct`] or [`Variant`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Field {
+    /// The name of the field.
+    pub name: String,
+    /// The layout of the field.
+    pub schema: Schema,
+    /// Bytes preceding the field, corresponding to the `magic` attribute.
+    pub magic: Vec<u8>,
+    /// Bits of padding preceding the field, corresponding to the `pad_before` attribute.
+    pub pad_before: u32,
+    /// Bits of padding following the field, corresponding to the `pad_after` attribute.
+    pub pad_after: u32,
+}
+
+/// A variant of a [`Schema::Enum`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Variant {
+    /// The name of the variant.
+    pub name: String,
+    /// The discriminant of the variant, corresponding to the `discriminant` attribute.
+    pub discriminant: Value,
+    /// The fields of the variant.
+    pub fields: Vec<Field>,
+}
+
+/// A type whose layout can be described by a [`Schema`].
+///
+/// Can be derived for structs and enums, alongside [`BitDecode`] and [`BitEncode`].
+pub trait Describe {
+    /// The layout of this type.
+    fn schema() -> Schema;
+}
+
+/// A type whose layout, when tagged with a length, can be described by a [`Schema`].
+pub trait DescribeTagged {
+    /// The layout of this type with the given length.
+    fn tagged_schema(length: Length) -> Schema;
+}
+
+impl Field {
+    /// Creates a field without magic or padding.
+    pub fn new(name: impl Into<String>, schema: Schema) -> Self {
+        Self {
+            name: name.into(),
+            schema,
+            magic: Vec::new(),
+            pad_before: 0,
+            pad_after: 0,
+        }
+    }
+}
+
+impl Schema {
+    /// Decodes a value with this layout.
+    pub fn decode<R, E>(&self, read: &mut R) -> Result<Value>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        self.decode_in::<_, E, _>(read, &[], &mut ())
+    }
+
+    /// Decodes a value with this layout from bytes, returning the value and the number of bits
+    /// read.
+    pub fn decode_bytes<E>(&self, bytes: &[u8], byte_order: E) -> Result<(Value, u64)>
+    where
+        E: Endianness,
+    {
+        let mut buffer = BitReader::endian(io::Cursor::new(bytes), byte_order);
+        let value = self.decode::<_, E>(&mut buffer)?;
+        Ok((value, buffer.position_in_bits()?))
+    }
+
+    /// Encodes a value with this layout, returning [`Error::Schema`] if it doesn't match.
+    pub fn encode<W, E>(&self, value: &Value, write: &mut W) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        match (self, value) {
+            (Self::Bool, Value::Bool(value)) => value.encode::<_, E>(write, &mut (), ()),
+            (Self::U8, Value::UInt(value)) => {
+                u8::try_from(*value)?.encode::<_, E>(write, &mut (), ())
+            }
+            (Self::U16, Value::UInt(value)) => {
+                u16::try_from(*value)?.encode::<_, E>(write, &mut (), ())
+            }
+            (Self::U32, Value::UInt(value)) => {
+                u32::try_from(*value)?.encode::<_, E>(write, &mut (), ())
+            }
+            (Self::U64, Value::UInt(value)) => value.encode::<_, E>(write, &mut (), ()),
+            (Self::I8, Value::Int(value)) => {
+                i8::try_from(*value)?.encode::<_, E>(write, &mut (), ())
+            }
+            (Self::I16, Value::Int(value)) => {
+                i16::try_from(*value)?.encode::<_, E>(write, &mut (), ())
+            }
+            (Self::I32, Value::Int(value)) => {
+                i32::try_from(*value)?.encode::<_, E>(write, &mut (), ())
+            }
+            (Self::I64, Value::Int(value)) => value.encode::<_, E>(write, &mut (), ()),
+            (
+                Self::Bits {
+                    width,
+                    signed: false,
+                },
+                Value::UInt(value),
+            ) => Ok(write.write_var(*width, *value)?),
+            (
+                Self::Bits {
+                    width,
+                    signed: true,
+                },
+                Value::Int(value),
+            ) => Ok(write.write_signed_var(*width, *value)?),
+            (Self::Bytes(length), Value::Bytes(bytes)) => {
+                length.encode::<_, E>(bytes.len(), write)?;
+                Ok(write.write_bytes(bytes)?)
+            }
+            (Self::String(length), Value::String(string)) => {
+                length.encode::<_, E>(string.len(), write)?;
+                Ok(write.write_bytes(string.as_bytes())?)
+            }
+            (Self::Array { item, length }, Value::Array(items)) => {
+                length.encode::<_, E>(items.len(), write)?;
+                for value in items {
+                    item.encode::<_, E>(value, write)?;
+                }
+                Ok(())
+            }
+            (Self::Struct(fields), Value::Struct(values)) => {
+                encode_fields::<_, E>(fields, values, write)
+            }
+            (
+                Self::Enum {
+                    discriminant,
+                    variants,
+                },
+                Value::Variant { name, fields },
+            ) => {
+                let variant = variants
+                    .iter()
+                    .find(|variant| variant.name == *name)
+                    .ok_or(Error::Schema("unknown variant"))?;
+                discriminant.encode::<_, E>(&variant.discriminant, write)?;
+                encode_fields::<_, E>(&variant.fields, fields, write)
+            }
+            _ => Err(Error::Schema("value does not match schema")),
+        }
+    }
+
+    /// Encodes a value with this layout into bytes.
+    pub fn encode_bytes<E>(&self, value: &Value, byte_order: E) -> Result<Vec<u8>>
+    where
+        E: Endianness,
+    {
+        let mut data = Vec::new();
+        let mut writer = BitWriter::endian(&mut data, byte_order);
+        self.encode::<_, E>(value, &mut writer)?;
+        writer.byte_align()?;
+        Ok(data)
+    }
+
+    fn decode_in<R, E, T>(
+        &self,
+        read: &mut R,
+        scope: &[(String, Value)],
+        tracer: &mut T,
+    ) -> Result<Value>
+    where
+        R: BitRead,
+        E: Endianness,
+        T: Tracer<R>,
+    {
+        Ok(match self {
+            Self::Bool => Value::Bool(bool::decode::<_, E>(read, &mut (), ())?),
+            Self::U8 => Value::UInt(u8::decode::<_, E>(read, &mut (), ())?.into()),
+            Self::U16 => Value::UInt(u16::decode::<_, E>(read, &mut (), ())?.into()),
+            Self::U32 => Value::UInt(u32::decode::<_, E>(read, &mut (), ())?.into()),
+            Self::U64 => Value::UInt(u64::decode::<_, E>(read, &mut (), ())?),
+            Self::I8 => Value::Int(i8::decode::<_, E>(read, &mut (), ())?.into()),
+            Self::I16 => Value::Int(i16::decode::<_, E>(read, &mut (), ())?.into()),
+            Self::I32 => Value::Int(i32::decode::<_, E>(read, &mut (), ())?.into()),
+            Self::I64 => Value::Int(i64::decode::<_, E>(read, &mut (), ())?),
+            Self::Bits {
+                width,
+                signed: false,
+            } => Value::UInt(read.read_var(*width)?),
+            Self::Bits {
+                width,
+                signed: true,
+            } => Value::Int(read.read_signed_var(*width)?),
+            Self::Bytes(length) => Value::Bytes(length.decode_items::<_, E, _, _>(
+                read,
+                scope,
+                &mut (),
+                |read, (), _| Ok(read.read_to::<u8>()?),
+            )?),
+            Self::String(length) => Value::String(String::from_utf8(
+                length.decode_items::<_, E, _, _>(read, scope, &mut (), |read, (), _| {
+                    Ok(read.read_to::<u8>()?)
+                })?,
+            )?),
+            Self::Array { item, length } => Value::Array(length.decode_items::<_, E, _, _>(
+                read,
+                scope,
+                tracer,
+                |read, tracer, index| {
+                    tracer.enter(read, &index.to_string());
+                    let value = item.decode_in::<_, E, _>(read, &[], tracer)?;
+                    tracer.exit(read, &value);
+                    Ok(value)
+                },
+            )?),
+            Self::Struct(fields) => Value::Struct(decode_fields::<_, E, _>(fields, read, tracer)?),
+            Self::Enum {
+                discriminant,
+                variants,
+            } => {
+                tracer.enter(read, "(discriminant)");
+                let discriminant = discriminant.decode::<_, E>(read)?;
+                tracer.exit(read, &discriminant);
+                let variant = variants
+                    .iter()
+                    .find(|variant| variant.discriminant == discriminant)
+                    .ok_or(Error::Discriminant)?;
+                Value::Variant {
+                    name: variant.name.clone(),
+                    fields: decode_fields::<_, E, _>(&variant.fields, read, tracer)?,
+                }
+            }
+        })
+    }
+
+    /// Whether values with this layout are [`Value::Int`]s rather than [`Value::UInt`]s.
+    #[must_use]
+    pub const fn is_signed(&self) -> bool {
+        matches!(
+            self,
+            Self::I8 | Self::I16 | Self::I32 | Self::I64 | Self::Bits { signed: true, .. }
+        )
+    }
+
+    /// The number of bits in values with this layout, or [`None`] if it varies.
+    #[must_use]
+    pub fn bit_width(&self) -> Option<u64> {
+        match self {
+            Self::Bool | Self::U8 | Self::I8 => Some(8),
+            Self::U16 | Self::I16 => Some(16),
+            Self::U32 | Self::I32 => Some(32),
+            Self::U64 | Self::I64 => Some(64),
+            Self::Bits { width, .. } => Some((*width).into()),
+            Self::Bytes(Length::Fixed(count)) | Self::String(Length::Fixed(count)) => {
+                u64::try_from(*count).ok()?.checked_mul(8)
+            }
+            Self::Array {
+                item,
+                length: Length::Fixed(count),
+            } => item.bit_width()?.checked_mul(u64::try_from(*count).ok()?),
+            Self::Struct(fields) => fields
+                .iter()
+                .try_fold(0u64, |width, field| width.checked_add(field.bit_width()?)),
+            Self::Enum {
+                discriminant,
+                variants,
+            } if variants.iter().all(|variant| variant.fields.is_empty()) => {
+                discriminant.bit_width()
+            }
+            Self::Bytes(_) | Self::String(_) | Self::Array { .. } | Self::Enum { .. } => None,
+        }
+    }
+
+    /// Renders an RFC-style diagram of this layout, 32 bits per row.
+    ///
+    /// Fields are labelled by name, magic by its bytes, and padding as `padding`. Fields whose
+    /// width varies, and the fields of enum variants, are bordered by `/` and fill the rest of
+    /// their row.
+    ///
+    /// ```
+    /// # use bin_proto::dynamic::{Field, Length, Schema};
+    /// let schema = Schema::Struct(vec![
+    ///     Field::new("version", Schema::Bits { width: 4, signed: false }),
+    ///     Field::new("flags", Schema::Bits { width: 4, signed: false }),
+    ///     Field::new("ttl", Schema::U8),
+    ///     Field::new("length", Schema::U16),
+    ///     Field::new("id", Schema::U16),
+    ///     Field::new("data", Schema::Bytes(Length::Untagged)),
+    /// ]);
+    /// assert_eq!(
+    ///     schema.diagram(),
+    ///     r" 0                   1                   2                   3
+    ///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+    /// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+    /// |version| flags |      ttl      |            length             |
+    /// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+    /// |              id               /             data              /
+    /// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+    /// ",
+    /// );
+    /// ```
+    #[must_use]
+    pub fn diagram(&self) -> String {
+        let mut segments = Vec::new();
+        self.segments("", &mut segments);
+        render_diagram(&segments)
+    }
+
+    fn segments(&self, name: &str, segments: &mut Vec<Segment>) {
+        match self {
+            Self::Struct(fields) => {
+                for field in fields {
+                    field.segments(segments);
+                }
+            }
+            Self::Enum {
+                discriminant,
+                variants,
+            } if variants.iter().any(|variant| !variant.fields.is_empty()) => {
+                discriminant.segments(name, segments);
+                segments.push(Segment {
+                    label: format!("{name} fields"),
+                    width: None,
+                });
+            }
+            _ => segments.push(Segment {
+                label: name.into(),
+                width: self.bit_width(),
+            }),
+        }
+    }
+}
+
+impl Field {
+    fn bit_width(&self) -> Option<u64> {
+        let magic = u64::try_from(self.magic.len()).ok()?.checked_mul(8)?;
+        [self.pad_before.into(), magic, self.pad_after.into()]
+            .into_iter()
+            .try_fold(self.schema.bit_width()?, u64::checked_add)
+    }
+
+    fn segments(&self, segments: &mut Vec<Segment>) {
+        let padding = |width: u32| Segment {
+            label: "padding".into(),
+            width: Some(width.into()),
+        };
+        if self.pad_before > 0 {
+            segments.push(padding(self.pad_before));
+        }
+        if !self.magic.is_empty() {
+            let label = if self.magic.iter().all(u8::is_ascii_graphic) {
+                format!("{:?}", String::from_utf8_lossy(&self.magic))
+            } else {
+                self.magic.iter().fold(String::from("0x"), |label, byte| {
+                    format!("{label}{byte:02x}")
+                })
+            };
+            segments.push(Segment {
+                label,
+                width: Some(u64::try_from(self.magic.len()).unwrap_or(u64::MAX) * 8),
+            });
+        }
+        self.schema.segments(&self.name, segments);
+        if self.pad_after > 0 {
+            segments.push(padding(self.pad_after));
+        }
+    }
+}
+
+impl Length {
+    fn decode_items<R, E, T, I>(
+        &self,
+        read: &mut R,
+        scope: &[(String, Value)],
+        tracer: &mut T,
+        mut decode_item: impl FnMut(&mut R, &mut T, usize) -> Result<I>,
+    ) -> Result<Vec<I>>
+    where
+        R: BitRead,
+        E: Endianness,
+        T: Tracer<R>,
+    {
+        let count = match self {
+            Self::Fixed(count) => *count,
+            Self::Field(name) => scope
+                .iter()
+                .rev()
+                .find(|(field, _)| field == name)
+                .ok_or(Error::Schema("unknown length field"))
+                .and_then(|(_, value)| value_to_count(value))?,
+            Self::Prefix(schema) => value_to_count(&schema.decode::<_, E>(read)?)?,
+            Self::Untagged => {
+                let mut items = Vec::new();
+                loop {
+                    let mark = tracer.mark();
+                    match decode_item(read, tracer, items.len()) {
+                        Err(Error::Io(e)) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                            tracer.rewind(mark);
+                            return Ok(items);
+                        }
+                        item => {
+                            limits::reserve::<I>(items.len(), 1)?;
+                            items.push(item?);
+                        }
+                    }
+                }
+            }
+        };
+        limits::reserve::<I>(0, count)?;
+        let mut items = Vec::with_capacity(limits::capacity::<I>(count));
+        for index in 0..count {
+            items.push(decode_item(read, tracer, index)?);
+        }
+        Ok(items)
+    }
+
+    fn encode<W, E>(&self, count: usize, write: &mut W) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        match self {
+            Self::Fixed(fixed) if *fixed != count => {
+                Err(Error::Schema("length is not fixed length"))
+            }
+            Self::Fixed(_) | Self::Field(_) | Self::Untagged => Ok(()),
+            Self::Prefix(schema) => {
+                let count = if schema.is_signed() {
+                    Value::Int(count.try_into()?)
+                } else {
+                    Value::UInt(count.try_into()?)
+                };
+                schema.encode::<_, E>(&count, write)
+            }
+        }
+    }
+}
+
+fn value_to_count(value: &Value) -> Result<usize> {
+    match *value {
+        Value::UInt(count) => Ok(count.try_into()?),
+        Value::Int(count) => Ok(count.try_into()?),
+        _ => Err(Error::TagConvert),
+    }
+}
+
+fn decode_fields<R, E, T>(
+    fields: &[Field],
+    read: &mut R,
+    tracer: &mut T,
+) -> Result<Vec<(String, Value)>>
+where
+    R: BitRead,
+    E: Endianness,
+    T: Tracer<R>,
+{
+    let mut values = Vec::with_capacity(fields.len());
+    for field in fields {
+        decode_padding(read, field.pad_before, tracer)?;
+        if !field.magic.is_empty() {
+            tracer.enter(read, "(magic)");
+            let magic = read.read_to_vec(field.magic.len())?;
+            tracer.exit(read, &Value::Bytes(magic.clone()));
+            if magic != field.magic {
+                return Err(Error::Schema("magic mismatch"));
+            }
+        }
+        tracer.enter(read, &field.name);
+        let value = field.schema.decode_in::<_, E, _>(read, &values, tracer)?;
+        tracer.exit(read, &value);
+        decode_padding(read, field.pad_after, tracer)?;
+        values.push((field.name.clone(), value));
+    }
+    Ok(values)
+}
+
+/// Reads `bits` of padding, as bytes followed by any remaining bits, so that they can be traced.
+fn decode_padding<R, T>(read: &mut R, bits: u32, tracer: &mut T) -> Result<()>
+where
+    R: BitRead,
+    T: Tracer<R>,
+{
+    if bits == 0 {
+        return Ok(());
+    }
+    tracer.enter(read, "(padding)");
+    let mut padding = Vec::new();
+    for _ in 0..bits / 8 {
+        padding.push(read.read_to::<u8>()?);
+    }
+    if bits % 8 != 0 {
+        padding.push(read.read_var::<u8>(bits % 8)?);
+    }
+    tracer.exit(read, &Value::Bytes(padding));
+    Ok(())
+}
+
+fn encode_fields<W, E>(fields: &[Field], values: &[(String, Value)], write: &mut W) -> Result<()>
+where
+    W: BitWrite,
+    E: Endianness,
+{
+    if fields.len() != values.len() {
+        return Err(Error::Schema("number of fields does not match schema"));
+    }
+    for (field, (name, value)) in fields.iter().zip(values) {
+        if field.name != *name {
+            return Err(Error::Schema("field name does not match schema"));
+        }
+        write.pad(field.pad_before)?;
+        write.write_bytes(&field.magic)?;
+        field.schema.encode::<_, E>(value, write)?;
+        write.pad(field.pad_after)?;
+    }
+    Ok(())
+}
+
+/// Records the fields of structs and enum variants, and the items of arrays, as they are decoded,
+/// along with the discriminants of enums and the magic and padding of fields.
+trait Tracer<R> {
+    /// Called before decoding the value of a field or item.
+    fn enter(&mut self, read: &mut R, name: &str);
+
+    /// Called after decoding the value of a field or item.
+    fn exit(&mut self, read: &mut R, value: &Value);
+
+    /// Returns a mark for discarding the fields recorded after it.
+    fn mark(&self) -> usize;
+
+    /// Discards the fields recorded after `mark`, such as those of an untagged item that ran into
+    /// the end of the stream.
+    fn rewind(&mut self, mark: usize);
+}
+
+impl<R> Tracer<R> for () {
+    fn enter(&mut self, _: &mut R, _: &str) {}
+
+    fn exit(&mut self, _: &mut R, _: &Value) {}
+
+    fn mark(&self) -> usize {
+        0
+    }
+
+    fn rewind(&mut self, _: usize) {}
+}
+
+const DIAGRAM_WIDTH: u64 = 32;
+
+/// A labelled run of bits in a diagram, of [`None`] width if it varies.
+struct Segment {
+    label: String,
+    width: Option<u64>,
+}
+
+/// A part of a [`Segment`] within a single row of a diagram.
+struct Cell<'a> {
+    label: &'a str,
+    width: u64,
+    variable: bool,
+}
+
+fn render_diagram(segments: &[Segment]) -> String {
+    let mut rows: Vec<Vec<Cell>> = Vec::new();
+    let mut offset = 0;
+    for segment in segments {
+        if offset == 0 {
+            rows.push(Vec::new());
+        }
+        let Some(mut remaining) = segment.width else {
+            if let Some(row) = rows.last_mut() {
+                row.push(Cell {
+                    label: &segment.label,
+                    width: DIAGRAM_WIDTH - offset,
+                    variable: true,
+                });
+            }
+            offset = 0;
+            continue;
+        };
+        let mut label = segment.label.as_str();
+        while remaining > 0 {
+            if offset == 0 && rows.last().is_some_and(|row| !row.is_empty()) {
+                rows.push(Vec::new());
+            }
+            let width = remaining.min(DIAGRAM_WIDTH - offset);
+            if let Some(row) = rows.last_mut() {
+                row.push(Cell {
+                    label,
+                    width,
+                    variable: false,
+                });
+            }
+            label = "";
+            remaining -= width;
+            offset = (offset + width) % DIAGRAM_WIDTH;
+        }
+    }
+    rows.retain(|row| !row.is_empty());
+
+    let row_width = |row: &[Cell]| row.iter().map(|cell| cell.width).sum::<u64>();
+    let border = |width: u64| {
+        let mut line = String::from("+");
+        for _ in 0..width {
+            line.push_str("-+");
+        }
+        line.push('\n');
+        line
+    };
+
+    let width = rows.iter().map(|row| row_width(row)).max().unwrap_or(0);
+    let mut diagram = String::new();
+    let mut tens = String::new();
+    let mut units = String::new();
+    for bit in 0..width {
+        tens.push(' ');
+        tens.push(if bit % 10 == 0 { digit(bit / 10) } else { ' ' });
+        units.push(' ');
+        units.push(digit(bit % 10));
+    }
+    for line in [tens, units] {
+        diagram.push_str(line.trim_end());
+        diagram.push('\n');
+    }
+    if let Some(first) = rows.first() {
+        diagram.push_str(&border(row_width(first)));
+    }
+    for row in &rows {
+        for cell in row {
+            diagram.push(if cell.variable { '/' } else { '|' });
+            let space = usize::try_from(cell.width * 2 - 1).unwrap_or(usize::MAX);
+            let label: String = cell.label.chars().take(space).collect();
+            let left = (space - label.chars().count()) / 2;
+            diagram.extend(core::iter::repeat_n(' ', left));
+            diagram.push_str(&label);
+            diagram.extend(core::iter::repeat_n(
+                ' ',
+                space - left - label.chars().count(),
+            ));
+        }
+        diagram.push(if row.last().is_some_and(|cell| cell.variable) {
+            '/'
+        } else {
+            '|'
+        });
+        diagram.push('\n');
+        diagram.push_str(&border(row_width(row)));
+    }
+    diagram
+}
+
+fn digit(value: u64) -> char {
+    u32::try_from(value % 10)
+        .ok()
+        .and_then(|value| char::from_digit(value, 10))
+        .unwrap_or('?')
+}
+
+macro_rules! impl_describe {
+    ($($ty:ty => $schema:ident, $value:ident;)*) => {
+        $(
+            impl Describe for $ty {
+                fn schema() -> Schema {
+                    Schema::$schema
+                }
+            }
+
+            impl From<$ty> for Value {
+                fn from(value: $ty) -> Self {
+                    Self::$value(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_describe!(
+    bool => Bool, Bool;
+    u8 => U8, UInt;
+    u16 => U16, UInt;
+    u32 => U32, UInt;
+    u64 => U64, UInt;
+    i8 => I8, Int;
+    i16 => I16, Int;
+    i32 => I32, Int;
+    i64 => I64, Int;
+);
+
+impl<T: Describe, const N: usize> Describe for [T; N] {
+    fn schema() -> Schema {
+        Schema::Array {
+            item: Box::new(T::schema()),
+            length: Length::Fixed(N),
+        }
+    }
+}
+
+impl<T: Describe> DescribeTagged for Vec<T> {
+    fn tagged_schema(length: Length) -> Schema {
+        Schema::Array {
+            item: Box::new(T::schema()),
+            length,
+        }
+    }
+}
+
+impl DescribeTagged for String {
+    fn tagged_schema(length: Length) -> Schema {
+        Schema::String(length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bitstream_io::BigEndian;
+
+    fn tagged() -> Schema {
+        Schema::Struct(alloc::vec![
+            Field::new("len", Schema::U8),
+            Field::new(
+                "items",
+                Schema::Array {
+                    item: Box::new(Schema::I16),
+                    length: Length::Field("len".into()),
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn decode_tag_field() {
+        assert_eq!(
+            tagged()
+                .decode_bytes(&[2, 0xff, 0xfe, 0x00, 0x01], BigEndian)
+                .unwrap(),
+            (
+                Value::Struct(alloc::vec![
+                    ("len".into(), Value::UInt(2)),
+                    (
+                        "items".into(),
+                        Value::Array(alloc::vec![Value::Int(-2), Value::Int(1)])
+                    ),
+                ]),
+                40
+            )
+        );
+    }
+
+    #[test]
+    fn decode_untagged() {
+        assert_eq!(
+            Schema::Bytes(Length::Untagged)
+                .decode_bytes(&[1, 2, 3], BigEndian)
+                .unwrap(),
+            (Value::Bytes(alloc::vec![1, 2, 3]), 24)
+        );
+    }
+
+    #[test]
+    fn encode_mismatch() {
+        assert!(matches!(
+            Schema::U8.encode_bytes(&Value::Int(1), BigEndian),
+            Err(Error::Schema(_))
+        ));
+        assert!(matches!(
+            Schema::U8.encode_bytes(&Value::UInt(256), BigEndian),
+            Err(Error::TryFromInt(_))
+        ));
+    }
+
+    #[test]
+    fn encode_signed_prefix() {
+        assert_eq!(
+            Schema::Bytes(Length::Prefix(Box::new(Schema::I8)))
+                .encode_bytes(&Value::Bytes(alloc::vec![7]), BigEndian)
+                .unwrap(),
+            [1, 7]
+        );
+    }
+}
@@ -18,8 +18,13 @@ pub enum Error {
     #[cfg(feature = "alloc")]
     FromUtf8(alloc::string::FromUtf8Error),
     #[cfg(feature = "alloc")]
+    FromUtf16(alloc::string::FromUtf16Error),
+    #[cfg(feature = "alloc")]
     Nul(alloc::ffi::NulError),
+    Utf8(core::str::Utf8Error),
     TryFromInt(core::num::TryFromIntError),
+    CharTryFrom(core::char::CharTryFromError),
+    ParseChar(core::char::ParseCharError),
     Borrow(core::cell::BorrowError),
     Discriminant,
     TagConvert,
@@ -31,6 +36,42 @@ pub enum Error {
     },
     EncodeSkipped,
     Magic(&'static [u8]),
+    /// A bit of padding did not have the expected value, at `offset` bits from the start of the
+    /// padding
+    Padding {
+        offset: u32,
+    },
+    /// A value with a `bit_order` did not start on a byte boundary
+    Unaligned,
+    /// A character cannot be represented in the target encoding
+    Unrepresentable(char),
+    /// A collection did not end with the delimiting item that it includes
+    Delimiter,
+    /// A decoded byte was not ASCII
+    NonAscii(u8),
+    /// A value is longer than the fixed length of its encoding
+    Length {
+        max: usize,
+        actual: usize,
+    },
+    /// Decoded flags contained bits that do not correspond to any defined flag
+    UnknownFlags,
+    /// A value was not valid BER
+    Ber(&'static str),
+    /// A value was not valid protobuf
+    Protobuf(&'static str),
+    /// A value did not match a [`Schema`](crate::dynamic::Schema)
+    Schema(&'static str),
+    /// A [`Limits`](crate::limits::Limits) value was exceeded
+    Limit(crate::limits::Limit),
+    /// The nanoseconds of a decoded [`Duration`](core::time::Duration) were not less than one
+    /// second
+    Nanos(u32),
+    /// A decoded value did not satisfy an assertion
+    Validation {
+        field: &'static str,
+        message: &'static str,
+    },
     #[cfg(feature = "alloc")]
     /// A catch-all for errors generated by user code
     Boxed(Box<dyn core::error::Error + Send + Sync>),
@@ -45,14 +86,29 @@ impl fmt::Display for Error {
             #[cfg(feature = "alloc")]
             Self::FromUtf8(e) => write!(f, "{e}"),
             #[cfg(feature = "alloc")]
+            Self::FromUtf16(e) => write!(f, "{e}"),
+            #[cfg(feature = "alloc")]
             Self::Nul(e) => write!(f, "{e}"),
+            Self::Utf8(e) => write!(f, "{e}"),
             Self::TryFromInt(e) => write!(f, "{e}"),
+            Self::CharTryFrom(e) => write!(f, "{e}"),
+            Self::ParseChar(e) => write!(f, "{e}"),
             Self::Borrow(e) => write!(f, "{e}"),
             Self::Discriminant => write!(f, "unknown enum discriminant"),
             Self::TagConvert => write!(f, "failed to convert tag"),
             #[cfg(feature = "std")]
             Self::Poison => write!(f, "poisoned lock"),
             Self::Magic(expected) => write!(f, "magic mismatch. Expected: {expected:?}."),
+            Self::Padding { offset } => {
+                write!(f, "unexpected padding value at bit {offset} of padding")
+            }
+            Self::Unaligned => write!(f, "bit_order value does not start on a byte boundary"),
+            Self::Unrepresentable(c) => write!(f, "unrepresentable character {c:?}"),
+            Self::Delimiter => write!(f, "collection does not end with its delimiting item"),
+            Self::NonAscii(byte) => write!(f, "non-ASCII byte {byte:#04x}"),
+            Self::Length { max, actual } => {
+                write!(f, "length {actual} exceeds maximum of {max}")
+            }
             Self::Underrun {
                 read_bits: read,
                 available_bits: available,
@@ -60,6 +116,15 @@ impl fmt::Display for Error {
                 write!(f, "buffer underrun: read {read} of {available} bits")
             }
             Self::EncodeSkipped => write!(f, "attempted to encode skipped enum variant"),
+            Self::UnknownFlags => write!(f, "unknown flags set"),
+            Self::Ber(e) => write!(f, "invalid BER encoding: {e}"),
+            Self::Protobuf(e) => write!(f, "invalid protobuf encoding: {e}"),
+            Self::Schema(e) => write!(f, "schema mismatch: {e}"),
+            Self::Limit(limit) => write!(f, "decode limit exceeded: {limit}"),
+            Self::Nanos(nanos) => write!(f, "nanoseconds {nanos} exceed one second"),
+            Self::Validation { field, message } => {
+                write!(f, "validation of {field} failed: {message}")
+            }
             #[cfg(feature = "alloc")]
             Self::Boxed(e) => write!(f, "{e}"),
             Self::Other(e) => write!(f, "other: {e}"),
@@ -82,6 +147,14 @@ impl From<alloc::string::FromUtf8Error> for Error {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl From<alloc::string::FromUtf16Error> for Error {
+    #[inline]
+    fn from(value: alloc::string::FromUtf16Error) -> Self {
+        Self::FromUtf16(value)
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl From<alloc::ffi::NulError> for Error {
     #[inline]
@@ -90,6 +163,27 @@ impl From<alloc::ffi::NulError> for Error {
     }
 }
 
+impl From<core::str::Utf8Error> for Error {
+    #[inline]
+    fn from(value: core::str::Utf8Error) -> Self {
+        Self::Utf8(value)
+    }
+}
+
+impl From<core::char::CharTryFromError> for Error {
+    #[inline]
+    fn from(value: core::char::CharTryFromError) -> Self {
+        Self::CharTryFrom(value)
+    }
+}
+
+impl From<core::char::ParseCharError> for Error {
+    #[inline]
+    fn from(value: core::char::ParseCharError) -> Self {
+        Self::ParseChar(value)
+    }
+}
+
 impl From<core::num::TryFromIntError> for Error {
     #[inline]
     fn from(value: core::num::TryFromIntError) -> Self {
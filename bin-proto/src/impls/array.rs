@@ -78,7 +78,7 @@ mod test {
 
     struct MustDrop<'a>(&'a RefCell<MustDropState>);
 
-    impl<'a> Drop for MustDrop<'a> {
+    impl Drop for MustDrop<'_> {
         fn drop(&mut self) {
             self.0.borrow_mut().dropped = true;
         }
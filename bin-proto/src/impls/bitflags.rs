@@ -0,0 +1,170 @@
+#![cfg(feature = "bitflags")]
+
+/// Implements [`BitDecode`](crate::BitDecode) and [`BitEncode`](crate::BitEncode) for a type
+/// generated by [`bitflags::bitflags`], both with and without [`Bits`](crate::Bits).
+///
+/// By default, unknown bits are preserved when decoding. If `strict` is specified, decoding
+/// unknown bits returns [`Error::UnknownFlags`](crate::Error::UnknownFlags).
+///
+/// ```
+/// # use bin_proto::{BitCodec, BitDecode, BitEncode};
+/// bitflags::bitflags! {
+///     #[derive(Debug, PartialEq, Eq)]
+///     struct Flags: u8 {
+///         const A = 0b01;
+///         const B = 0b10;
+///     }
+/// }
+///
+/// bin_proto::impl_bitflags!(Flags, strict);
+///
+/// assert_eq!(
+///     Flags::decode_bytes(&[0b11], bin_proto::BigEndian).unwrap(),
+///     (Flags::A | Flags::B, 8)
+/// );
+/// assert!(Flags::decode_bytes(&[0b100], bin_proto::BigEndian).is_err());
+/// ```
+#[macro_export]
+macro_rules! impl_bitflags {
+    ($ty:ty) => {
+        $crate::impl_bitflags!(@impl $ty, false);
+    };
+    ($ty:ty, strict) => {
+        $crate::impl_bitflags!(@impl $ty, true);
+    };
+    (@impl $ty:ty, $strict:expr) => {
+        impl<Ctx> $crate::BitDecode<Ctx> for $ty
+        where
+            <$ty as $crate::bitflags::Flags>::Bits: $crate::BitDecode<Ctx>,
+        {
+            fn decode<R, E>(read: &mut R, ctx: &mut Ctx, tag: ()) -> $crate::Result<Self>
+            where
+                R: $crate::BitRead,
+                E: $crate::Endianness,
+            {
+                $crate::util::flags_from_bits(
+                    $crate::BitDecode::decode::<_, E>(read, ctx, tag)?,
+                    $strict,
+                )
+            }
+        }
+
+        impl<Ctx, const C: u32> $crate::BitDecode<Ctx, $crate::Bits<C>> for $ty
+        where
+            <$ty as $crate::bitflags::Flags>::Bits: $crate::BitDecode<Ctx, $crate::Bits<C>>,
+        {
+            fn decode<R, E>(
+                read: &mut R,
+                ctx: &mut Ctx,
+                tag: $crate::Bits<C>,
+            ) -> $crate::Result<Self>
+            where
+                R: $crate::BitRead,
+                E: $crate::Endianness,
+            {
+                $crate::util::flags_from_bits(
+                    $crate::BitDecode::decode::<_, E>(read, ctx, tag)?,
+                    $strict,
+                )
+            }
+        }
+
+        impl<Ctx> $crate::BitEncode<Ctx> for $ty
+        where
+            <$ty as $crate::bitflags::Flags>::Bits: $crate::BitEncode<Ctx>,
+        {
+            fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, tag: ()) -> $crate::Result<()>
+            where
+                W: $crate::BitWrite,
+                E: $crate::Endianness,
+            {
+                $crate::BitEncode::encode::<_, E>(
+                    &$crate::bitflags::Flags::bits(self),
+                    write,
+                    ctx,
+                    tag,
+                )
+            }
+        }
+
+        impl<Ctx, const C: u32> $crate::BitEncode<Ctx, $crate::Bits<C>> for $ty
+        where
+            <$ty as $crate::bitflags::Flags>::Bits: $crate::BitEncode<Ctx, $crate::Bits<C>>,
+        {
+            fn encode<W, E>(
+                &self,
+                write: &mut W,
+                ctx: &mut Ctx,
+                tag: $crate::Bits<C>,
+            ) -> $crate::Result<()>
+            where
+                W: $crate::BitWrite,
+                E: $crate::Endianness,
+            {
+                $crate::BitEncode::encode::<_, E>(
+                    &$crate::bitflags::Flags::bits(self),
+                    write,
+                    ctx,
+                    tag,
+                )
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bits;
+
+    ::bitflags::bitflags! {
+        #[derive(Debug, PartialEq, Eq)]
+        struct Lenient: u8 {
+            const A = 0b0001;
+            const B = 0b0100;
+        }
+    }
+
+    ::bitflags::bitflags! {
+        #[derive(Debug, PartialEq, Eq)]
+        struct Strict: u8 {
+            const A = 0b0001;
+            const B = 0b0100;
+        }
+    }
+
+    crate::impl_bitflags!(Lenient);
+    crate::impl_bitflags!(Strict, strict);
+
+    mod lenient {
+        use super::*;
+
+        test_codec!(Lenient; Lenient::A | Lenient::from_bits_retain(0b1000) => [0b1001]);
+    }
+
+    mod lenient_bits {
+        use super::*;
+
+        test_codec!(Lenient| Bits::<8>, Bits::<8>; Lenient::A | Lenient::B => [0b0101]);
+    }
+
+    mod strict {
+        use super::*;
+
+        test_codec!(Strict; Strict::A | Strict::B => [0b0101]);
+
+        #[test]
+        fn unknown_flags() {
+            assert!(matches!(
+                <Strict as crate::BitDecode>::decode::<_, ::bitstream_io::BigEndian>(
+                    &mut ::bitstream_io::BitReader::endian(
+                        [0b1000u8].as_slice(),
+                        ::bitstream_io::BigEndian
+                    ),
+                    &mut (),
+                    (),
+                ),
+                Err(crate::Error::UnknownFlags)
+            ));
+        }
+    }
+}
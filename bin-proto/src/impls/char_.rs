@@ -0,0 +1,90 @@
+use bitstream_io::{BitRead, BitWrite, Endianness};
+
+use crate::{BitDecode, BitEncode, Result, Utf8};
+
+impl<Ctx> BitDecode<Ctx> for char {
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        Ok(Self::try_from(u32::decode::<_, E>(read, ctx, ())?)?)
+    }
+}
+
+impl<Ctx> BitEncode<Ctx> for char {
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        u32::from(*self).encode::<_, E>(write, ctx, ())
+    }
+}
+
+impl<Ctx> BitDecode<Ctx, Utf8> for char {
+    fn decode<R, E>(read: &mut R, _: &mut Ctx, _: Utf8) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let mut buf = [0; 4];
+        buf[0] = read.read_to::<u8>()?;
+        let len = match buf[0].leading_ones() {
+            2 => 2,
+            3 => 3,
+            4 => 4,
+            _ => 1,
+        };
+        read.read_bytes(&mut buf[1..len])?;
+        Ok(core::str::from_utf8(&buf[..len])?.parse()?)
+    }
+}
+
+impl<Ctx> BitEncode<Ctx, Utf8> for char {
+    fn encode<W, E>(&self, write: &mut W, _: &mut Ctx, _: Utf8) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        write.write_bytes(self.encode_utf8(&mut [0; 4]).as_bytes())?;
+        Ok(())
+    }
+}
+
+test_codec!(char; 'ł' => [0x00, 0x00, 0x01, 0x42]);
+test_roundtrip!(char);
+
+#[cfg(test)]
+mod utf8 {
+    use super::*;
+
+    test_codec!(char| Utf8, Utf8; 'ł' => [0xc5, 0x82]);
+
+    mod ascii {
+        use super::*;
+
+        test_codec!(char| Utf8, Utf8; 'a' => [0x61]);
+    }
+
+    mod four_bytes {
+        use super::*;
+
+        test_codec!(char| Utf8, Utf8; '🦀' => [0xf0, 0x9f, 0xa6, 0x80]);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(
+            <char as BitDecode<(), Utf8>>::decode::<_, bitstream_io::BigEndian>(
+                &mut bitstream_io::BitReader::endian(
+                    [0xc5u8, 0x41].as_slice(),
+                    bitstream_io::BigEndian
+                ),
+                &mut (),
+                Utf8,
+            ),
+            Err(crate::Error::Utf8(_))
+        ));
+    }
+}
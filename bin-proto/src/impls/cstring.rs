@@ -3,7 +3,7 @@
 use alloc::{ffi::CString, vec::Vec};
 use bitstream_io::{BitRead, BitWrite, Endianness};
 
-use crate::{util, BitDecode, BitEncode, Result};
+use crate::{limits, util, BitDecode, BitEncode, Result};
 
 impl<Ctx> BitDecode<Ctx> for CString {
     fn decode<R, E>(read: &mut R, ctx: &mut Ctx, tag: ()) -> Result<Self>
@@ -17,6 +17,7 @@ impl<Ctx> BitDecode<Ctx> for CString {
             if c == 0x00 {
                 return Ok(Self::new(result)?);
             }
+            limits::reserve::<u8>(result.len(), 1)?;
             result.push(c);
         }
     }
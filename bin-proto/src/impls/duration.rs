@@ -0,0 +1,52 @@
+use core::time::Duration;
+
+use bitstream_io::{BitRead, BitWrite, Endianness};
+
+use crate::{BitDecode, BitEncode, Error, Result};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+impl<Ctx> BitDecode<Ctx> for Duration {
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let secs = u64::decode::<_, E>(read, ctx, ())?;
+        let nanos = u32::decode::<_, E>(read, ctx, ())?;
+        if nanos >= NANOS_PER_SEC {
+            return Err(Error::Nanos(nanos));
+        }
+        Ok(Self::new(secs, nanos))
+    }
+}
+
+impl<Ctx> BitEncode<Ctx> for Duration {
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        self.as_secs().encode::<_, E>(write, ctx, ())?;
+        self.subsec_nanos().encode::<_, E>(write, ctx, ())
+    }
+}
+
+test_codec!(Duration; Duration::new(1, 2) => [0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2]);
+test_roundtrip!(Duration);
+
+#[cfg(test)]
+mod tests {
+    use bitstream_io::{BigEndian, BitReader};
+
+    use super::*;
+
+    #[test]
+    fn nanos_out_of_range() {
+        let bytes: &[u8] = &[0, 0, 0, 0, 0, 0, 0, 1, 0x3b, 0x9a, 0xca, 0x00];
+        assert!(matches!(
+            Duration::decode::<_, BigEndian>(&mut BitReader::endian(bytes, BigEndian), &mut (), ()),
+            Err(Error::Nanos(1_000_000_000))
+        ));
+    }
+}
@@ -0,0 +1,84 @@
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use bitstream_io::{BitRead, BitWrite, Endianness};
+
+use crate::{BitDecode, BitEncode, Error, Result, Untagged};
+
+impl<Tag, Ctx> BitDecode<Ctx, crate::Tag<Tag>> for IpAddr
+where
+    Tag: TryInto<u8>,
+{
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, tag: crate::Tag<Tag>) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        match tag.0.try_into().map_err(|_| Error::TagConvert)? {
+            4 => Ipv4Addr::decode::<_, E>(read, ctx, ()).map(Self::V4),
+            6 => Ipv6Addr::decode::<_, E>(read, ctx, ()).map(Self::V6),
+            _ => Err(Error::Discriminant),
+        }
+    }
+}
+
+impl<Ctx> BitEncode<Ctx, Untagged> for IpAddr {
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, _: Untagged) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        match self {
+            Self::V4(addr) => addr.encode::<_, E>(write, ctx, ()),
+            Self::V6(addr) => addr.encode::<_, E>(write, ctx, ()),
+        }
+    }
+}
+
+#[cfg(feature = "prepend-tags")]
+impl<Ctx> BitEncode<Ctx> for IpAddr {
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        let family: u8 = if self.is_ipv4() { 4 } else { 6 };
+        family.encode::<_, E>(write, ctx, ())?;
+        self.encode::<_, E>(write, ctx, Untagged)
+    }
+}
+
+#[cfg(feature = "prepend-tags")]
+impl<Ctx> BitDecode<Ctx> for IpAddr {
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let tag = u8::decode::<_, E>(read, ctx, ())?;
+        Self::decode::<_, E>(read, ctx, crate::Tag(tag))
+    }
+}
+
+#[cfg(test)]
+mod v4 {
+    use crate::Tag;
+
+    use super::*;
+
+    test_codec!(IpAddr| Untagged, Tag(4); IpAddr::V4(Ipv4Addr::new(192, 168, 1, 0)) => [192, 168, 1, 0]);
+}
+
+#[cfg(test)]
+mod v6 {
+    use crate::Tag;
+
+    use super::*;
+
+    test_codec!(IpAddr| Untagged, Tag(6);
+        IpAddr::V6(Ipv6Addr::LOCALHOST) =>
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]
+    );
+}
+
+#[cfg(feature = "prepend-tags")]
+test_roundtrip!(IpAddr);
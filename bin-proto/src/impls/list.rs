@@ -23,7 +23,8 @@ macro_rules! impl_read_list {
             {
                 let item_count = ::core::convert::TryInto::try_into(tag.0)
                     .map_err(|_| $crate::Error::TagConvert)?;
-                let mut this = ($new)(item_count);
+                $crate::limits::reserve::<T>(0, item_count)?;
+                let mut this = ($new)($crate::limits::capacity::<T>(item_count));
                 for _ in 0..item_count {
                     this.$push($crate::BitDecode::<_, _>::decode::<_, E>(read, ctx, ())?);
                 }
@@ -49,6 +50,149 @@ macro_rules! impl_read_list {
             }
         }
 
+        impl<Tag, Ctx, T, ItemTag, F, $($h)?>
+            $crate::BitDecode<Ctx, $crate::TagItems<$crate::Tag<Tag>, F>> for $ty<T, $($h)?>
+        where
+            T: $crate::BitDecode<Ctx, ItemTag> $(+ $tbound0 $(+ $tbound1)?)?,
+            Tag: ::core::convert::TryInto<usize>,
+            F: ::core::ops::FnMut(usize) -> ItemTag,
+            $($h: $hbound0 + $hbound1)?
+        {
+            fn decode<R, E>(
+                read: &mut R,
+                ctx: &mut Ctx,
+                mut tag: $crate::TagItems<$crate::Tag<Tag>, F>,
+            ) -> $crate::Result<Self>
+            where
+                R: ::bitstream_io::BitRead,
+                E: ::bitstream_io::Endianness,
+            {
+                let item_count = ::core::convert::TryInto::try_into(tag.0 .0)
+                    .map_err(|_| $crate::Error::TagConvert)?;
+                $crate::limits::reserve::<T>(0, item_count)?;
+                let mut this = ($new)($crate::limits::capacity::<T>(item_count));
+                for index in 0..item_count {
+                    this.$push($crate::BitDecode::<_, _>::decode::<_, E>(read, ctx, (tag.1)(index))?);
+                }
+                ::core::result::Result::Ok(this)
+            }
+        }
+
+        impl<Ctx, T, ItemTag, F, $($h)?>
+            $crate::BitDecode<Ctx, $crate::TagItems<$crate::Untagged, F>> for $ty<T, $($h)?>
+        where
+            T: $crate::BitDecode<Ctx, ItemTag> $(+ $tbound0 $(+ $tbound1)?)?,
+            F: ::core::ops::FnMut(usize) -> ItemTag,
+            $($h: $hbound0 + $hbound1)?
+        {
+            fn decode<R, E>(
+                read: &mut R,
+                ctx: &mut Ctx,
+                tag: $crate::TagItems<$crate::Untagged, F>,
+            ) -> $crate::Result<Self>
+            where
+                R: ::bitstream_io::BitRead,
+                E: ::bitstream_io::Endianness,
+            {
+                $crate::util::decode_tagged_items_to_eof::<_, E, _, _, _, _>(read, ctx, tag.1)
+                    .collect()
+            }
+        }
+
+        impl<Ctx, T, F, $($h)?> $crate::BitDecode<Ctx, $crate::Until<F>> for $ty<T, $($h)?>
+        where
+            T: $crate::BitDecode<Ctx> $(+ $tbound0 $(+ $tbound1)?)?,
+            F: ::core::ops::FnMut(&T) -> bool,
+            $($h: $hbound0 + $hbound1)?
+        {
+            fn decode<R, E>(
+                read: &mut R,
+                ctx: &mut Ctx,
+                tag: $crate::Until<F>,
+            ) -> $crate::Result<Self>
+            where
+                R: ::bitstream_io::BitRead,
+                E: ::bitstream_io::Endianness,
+            {
+                $crate::util::decode_items_until::<_, E, _, _, _>(read, ctx, tag.0, true).collect()
+            }
+        }
+
+        impl<Ctx, T, $($h)?> $crate::BitDecode<Ctx, $crate::Terminated<T>> for $ty<T, $($h)?>
+        where
+            T: $crate::BitDecode<Ctx> + ::core::cmp::PartialEq $(+ $tbound0 $(+ $tbound1)?)?,
+            $($h: $hbound0 + $hbound1)?
+        {
+            fn decode<R, E>(
+                read: &mut R,
+                ctx: &mut Ctx,
+                tag: $crate::Terminated<T>,
+            ) -> $crate::Result<Self>
+            where
+                R: ::bitstream_io::BitRead,
+                E: ::bitstream_io::Endianness,
+            {
+                $crate::util::decode_items_until::<_, E, _, _, _>(
+                    read,
+                    ctx,
+                    |item| *item == tag.0,
+                    false,
+                )
+                .collect()
+            }
+        }
+
+        impl<Ctx, T, F, $($h)?> $crate::BitDecode<Ctx, $crate::Delimited<$crate::Until<F>>>
+            for $ty<T, $($h)?>
+        where
+            T: $crate::BitDecode<Ctx> $(+ $tbound0 $(+ $tbound1)?)?,
+            F: ::core::ops::FnMut(&T) -> bool,
+            $($h: $hbound0 + $hbound1)?
+        {
+            fn decode<R, E>(
+                read: &mut R,
+                ctx: &mut Ctx,
+                tag: $crate::Delimited<$crate::Until<F>>,
+            ) -> $crate::Result<Self>
+            where
+                R: ::bitstream_io::BitRead,
+                E: ::bitstream_io::Endianness,
+            {
+                $crate::util::decode_items_until::<_, E, _, _, _>(
+                    read,
+                    ctx,
+                    tag.delimiter.0,
+                    tag.include,
+                )
+                .collect()
+            }
+        }
+
+        impl<Ctx, T, $($h)?> $crate::BitDecode<Ctx, $crate::Delimited<$crate::Terminated<T>>>
+            for $ty<T, $($h)?>
+        where
+            T: $crate::BitDecode<Ctx> + ::core::cmp::PartialEq $(+ $tbound0 $(+ $tbound1)?)?,
+            $($h: $hbound0 + $hbound1)?
+        {
+            fn decode<R, E>(
+                read: &mut R,
+                ctx: &mut Ctx,
+                tag: $crate::Delimited<$crate::Terminated<T>>,
+            ) -> $crate::Result<Self>
+            where
+                R: ::bitstream_io::BitRead,
+                E: ::bitstream_io::Endianness,
+            {
+                $crate::util::decode_items_until::<_, E, _, _, _>(
+                    read,
+                    ctx,
+                    |item| *item == tag.delimiter.0,
+                    tag.include,
+                )
+                .collect()
+            }
+        }
+
         #[cfg(feature = "prepend-tags")]
         impl<Ctx, T, $($h)?> $crate::BitDecode<Ctx> for $ty<T, $($h)?>
         where
@@ -91,6 +235,101 @@ macro_rules! impl_write_list {
             }
         }
 
+        impl<Ctx, T, ItemTag, F, $($h)?>
+            $crate::BitEncode<Ctx, $crate::TagItems<$crate::Untagged, F>> for $ty<T, $($h)?>
+        where
+            T: $crate::BitEncode<Ctx, ItemTag> $(+ $tbound0 $(+ $tbound1)?)?,
+            F: ::core::ops::FnMut(usize) -> ItemTag,
+        {
+            fn encode<W, E>(&self,
+                write: &mut W,
+                ctx: &mut Ctx,
+                tag: $crate::TagItems<$crate::Untagged, F>,
+            ) -> $crate::Result<()>
+            where
+                W: ::bitstream_io::BitWrite,
+                E: ::bitstream_io::Endianness,
+            {
+                $crate::util::encode_tagged_items::<_, E, _, _, _, _>(self.iter(), write, ctx, tag.1)
+            }
+        }
+
+        impl<Ctx, T, $($h)?> $crate::BitEncode<Ctx, $crate::Terminated<T>> for $ty<T, $($h)?>
+        where
+            T: $crate::BitEncode<Ctx> $(+ $tbound0 $(+ $tbound1)?)?
+        {
+            fn encode<W, E>(&self,
+                write: &mut W,
+                ctx: &mut Ctx,
+                tag: $crate::Terminated<T>,
+            ) -> $crate::Result<()>
+            where
+                W: ::bitstream_io::BitWrite,
+                E: ::bitstream_io::Endianness,
+            {
+                $crate::util::encode_items::<_, E, _, _>(self.iter(), write, ctx)?;
+                $crate::BitEncode::encode::<_, E>(&tag.0, write, ctx, ())
+            }
+        }
+
+        impl<Ctx, T, F, $($h)?> $crate::BitEncode<Ctx, $crate::Delimited<$crate::Until<F>>>
+            for $ty<T, $($h)?>
+        where
+            T: $crate::BitEncode<Ctx> $(+ $tbound0 $(+ $tbound1)?)?,
+            F: ::core::ops::FnMut(&T) -> bool,
+        {
+            fn encode<W, E>(&self,
+                write: &mut W,
+                ctx: &mut Ctx,
+                tag: $crate::Delimited<$crate::Until<F>>,
+            ) -> $crate::Result<()>
+            where
+                W: ::bitstream_io::BitWrite,
+                E: ::bitstream_io::Endianness,
+            {
+                let mut until = tag.delimiter.0;
+                let count = if tag.include && !tag.write {
+                    match self.iter().last() {
+                        Some(last) if until(last) => self.len() - 1,
+                        _ => return ::core::result::Result::Err($crate::Error::Delimiter),
+                    }
+                } else {
+                    self.len()
+                };
+                $crate::util::encode_items::<_, E, _, _>(self.iter().take(count), write, ctx)
+            }
+        }
+
+        impl<Ctx, T, $($h)?> $crate::BitEncode<Ctx, $crate::Delimited<$crate::Terminated<T>>>
+            for $ty<T, $($h)?>
+        where
+            T: $crate::BitEncode<Ctx> + ::core::cmp::PartialEq $(+ $tbound0 $(+ $tbound1)?)?
+        {
+            fn encode<W, E>(&self,
+                write: &mut W,
+                ctx: &mut Ctx,
+                tag: $crate::Delimited<$crate::Terminated<T>>,
+            ) -> $crate::Result<()>
+            where
+                W: ::bitstream_io::BitWrite,
+                E: ::bitstream_io::Endianness,
+            {
+                let count = if tag.include && !tag.write {
+                    match self.iter().last() {
+                        Some(last) if *last == tag.delimiter.0 => self.len() - 1,
+                        _ => return ::core::result::Result::Err($crate::Error::Delimiter),
+                    }
+                } else {
+                    self.len()
+                };
+                $crate::util::encode_items::<_, E, _, _>(self.iter().take(count), write, ctx)?;
+                if tag.write && !tag.include {
+                    $crate::BitEncode::encode::<_, E>(&tag.delimiter.0, write, ctx, ())?;
+                }
+                ::core::result::Result::Ok(())
+            }
+        }
+
         #[cfg(feature = "prepend-tags")]
         impl<Ctx, T, $($h)?> $crate::BitEncode<Ctx> for $ty<T, $($h)?>
         where
@@ -129,6 +368,115 @@ mod vec {
             Vec<u8>| Untagged, Tag(3); alloc::vec![1, 2, 3] => [0x01, 0x02, 0x03]
         );
 
+        mod terminated {
+            use crate::Terminated;
+
+            use super::*;
+
+            test_codec!(
+                Vec<u8>| Terminated(0), Terminated(0);
+                alloc::vec![1, 2, 3] => [0x01, 0x02, 0x03, 0x00]
+            );
+        }
+
+        mod until {
+            use crate::Until;
+
+            use super::*;
+
+            test_decode!(
+                Vec<u8>| Until(|item: &u8| *item >= 0x80);
+                [0x01, 0x02, 0x83, 0x04] => alloc::vec![1, 2, 0x83]
+            );
+        }
+
+        mod delimited_until {
+            use crate::{Delimited, Until};
+
+            use super::*;
+
+            test_encode!(
+                Vec<u8>| Delimited { delimiter: Until(|item: &u8| *item >= 0x80), include: true, write: false };
+                alloc::vec![1, 2, 0x83] => [0x01, 0x02]
+            );
+
+            #[test]
+            fn missing_delimiter() {
+                assert!(matches!(
+                    crate::BitEncode::encode::<_, bitstream_io::BigEndian>(
+                        &alloc::vec![1u8, 2],
+                        &mut bitstream_io::BitWriter::endian(
+                            &mut alloc::vec::Vec::new(),
+                            bitstream_io::BigEndian
+                        ),
+                        &mut (),
+                        Delimited {
+                            delimiter: Until(|item: &u8| *item >= 0x80),
+                            include: true,
+                            write: false
+                        },
+                    ),
+                    Err(crate::Error::Delimiter)
+                ));
+            }
+        }
+
+        mod delimited_until_excluded {
+            use crate::{Delimited, Until};
+
+            use super::*;
+
+            test_decode!(
+                Vec<u8>| Delimited { delimiter: Until(|item: &u8| *item >= 0x80), include: false, write: false };
+                [0x01, 0x02, 0x83, 0x04] => alloc::vec![1, 2]
+            );
+        }
+
+        mod delimited_terminated {
+            use crate::{Delimited, Terminated};
+
+            use super::*;
+
+            test_codec!(
+                Vec<u8>| Delimited { delimiter: Terminated(0), include: true, write: true },
+                Delimited { delimiter: Terminated(0), include: true, write: true };
+                alloc::vec![1, 2, 0] => [0x01, 0x02, 0x00]
+            );
+        }
+
+        mod delimited_terminated_unwritten {
+            use crate::{Delimited, Terminated};
+
+            use super::*;
+
+            test_encode!(
+                Vec<u8>| Delimited { delimiter: Terminated(0), include: false, write: false };
+                alloc::vec![1, 2] => [0x01, 0x02]
+            );
+        }
+
+        mod tag_items {
+            use crate::TagItems;
+
+            use super::*;
+
+            test_codec!(
+                Vec<Vec<u8>>| TagItems(Untagged, |_| Untagged), TagItems(Tag(2), |index| Tag(index + 1));
+                alloc::vec![alloc::vec![1], alloc::vec![2, 3]] => [0x01, 0x02, 0x03]
+            );
+        }
+
+        mod tag_items_untagged {
+            use crate::TagItems;
+
+            use super::*;
+
+            test_decode!(
+                Vec<[u8; 2]>| TagItems(Untagged, |_| ());
+                [0x01, 0x02, 0x03, 0x04] => alloc::vec![[1, 2], [3, 4]]
+            );
+        }
+
         #[cfg(feature = "prepend-tags")]
         test_roundtrip!(Vec::<i32>);
     }
@@ -1,9 +1,13 @@
 //! Implementations
 
 mod array;
+mod bitflags;
+mod char_;
 mod container;
 mod cstr;
 mod cstring;
+mod duration;
+mod ip_addr;
 mod ipv4;
 mod ipv6;
 mod list;
@@ -14,8 +18,11 @@ mod numerics;
 mod option;
 mod phantom_data;
 mod phantom_pinned;
+mod range;
 mod reference;
+mod result;
 mod slice;
+mod socket_addr;
 mod str_;
 mod string;
 mod tuple;
@@ -0,0 +1,79 @@
+use core::ops::{Range, RangeInclusive};
+
+use bitstream_io::{BitRead, BitWrite, Endianness};
+
+use crate::{BitDecode, BitEncode, Result};
+
+impl<Ctx, T> BitDecode<Ctx> for Range<T>
+where
+    T: BitDecode<Ctx>,
+{
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let start = T::decode::<_, E>(read, ctx, ())?;
+        let end = T::decode::<_, E>(read, ctx, ())?;
+        Ok(start..end)
+    }
+}
+
+impl<Ctx, T> BitEncode<Ctx> for Range<T>
+where
+    T: BitEncode<Ctx>,
+{
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        self.start.encode::<_, E>(write, ctx, ())?;
+        self.end.encode::<_, E>(write, ctx, ())
+    }
+}
+
+impl<Ctx, T> BitDecode<Ctx> for RangeInclusive<T>
+where
+    T: BitDecode<Ctx>,
+{
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let start = T::decode::<_, E>(read, ctx, ())?;
+        let end = T::decode::<_, E>(read, ctx, ())?;
+        Ok(start..=end)
+    }
+}
+
+impl<Ctx, T> BitEncode<Ctx> for RangeInclusive<T>
+where
+    T: BitEncode<Ctx>,
+{
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        self.start().encode::<_, E>(write, ctx, ())?;
+        self.end().encode::<_, E>(write, ctx, ())
+    }
+}
+
+#[cfg(test)]
+mod exclusive {
+    use super::*;
+
+    test_codec!(Range<u8>; 1..3 => [1, 3]);
+    test_roundtrip!(Range<u32>);
+}
+
+#[cfg(test)]
+mod inclusive {
+    use super::*;
+
+    test_codec!(RangeInclusive<u8>; 1..=3 => [1, 3]);
+    test_roundtrip!(RangeInclusive<u32>);
+}
@@ -0,0 +1,94 @@
+use bitstream_io::{BitRead, BitWrite, Endianness};
+
+use crate::{BitDecode, BitEncode, Error, Result, Untagged};
+
+impl<Tag, Ctx, T, U> BitDecode<Ctx, crate::Tag<Tag>> for core::result::Result<T, U>
+where
+    T: BitDecode<Ctx>,
+    U: BitDecode<Ctx>,
+    Tag: TryInto<bool>,
+{
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, tag: crate::Tag<Tag>) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        if tag.0.try_into().map_err(|_| Error::TagConvert)? {
+            let value = T::decode::<_, E>(read, ctx, ())?;
+            Ok(Ok(value))
+        } else {
+            let value = U::decode::<_, E>(read, ctx, ())?;
+            Ok(Err(value))
+        }
+    }
+}
+
+impl<Ctx, T, U> BitEncode<Ctx, Untagged> for core::result::Result<T, U>
+where
+    T: BitEncode<Ctx>,
+    U: BitEncode<Ctx>,
+{
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, _: Untagged) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        match *self {
+            Ok(ref value) => value.encode::<_, E>(write, ctx, ()),
+            Err(ref value) => value.encode::<_, E>(write, ctx, ()),
+        }
+    }
+}
+
+#[cfg(feature = "prepend-tags")]
+impl<Ctx, T, U> BitEncode<Ctx> for core::result::Result<T, U>
+where
+    T: BitEncode<Ctx>,
+    U: BitEncode<Ctx>,
+{
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        self.is_ok().encode::<_, E>(write, ctx, ())?;
+        self.encode::<_, E>(write, ctx, Untagged)
+    }
+}
+
+#[cfg(feature = "prepend-tags")]
+impl<Ctx, T, U> BitDecode<Ctx> for core::result::Result<T, U>
+where
+    T: BitDecode<Ctx>,
+    U: BitDecode<Ctx>,
+{
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let tag = bool::decode::<_, E>(read, ctx, ())?;
+        Self::decode::<_, E>(read, ctx, crate::Tag(tag))
+    }
+}
+
+#[cfg(test)]
+mod ok {
+    use crate::Tag;
+
+    use super::*;
+
+    test_codec!(core::result::Result<u8, u16>| Untagged, Tag(true); Ok(1) => [0x01]);
+}
+
+#[cfg(test)]
+mod err {
+    use crate::Tag;
+
+    use super::*;
+
+    test_codec!(core::result::Result<u8, u16>| Untagged, Tag(false); Err(2) => [0x00, 0x02]);
+}
+
+#[cfg(feature = "prepend-tags")]
+test_roundtrip!(core::result::Result::<i32, u8>);
@@ -0,0 +1,156 @@
+use core::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use bitstream_io::{BitRead, BitWrite, Endianness};
+
+use crate::{BitDecode, BitEncode, Error, Result, Untagged};
+
+impl<Ctx> BitDecode<Ctx> for SocketAddrV4 {
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let ip = Ipv4Addr::decode::<_, E>(read, ctx, ())?;
+        let port = u16::decode::<_, E>(read, ctx, ())?;
+        Ok(Self::new(ip, port))
+    }
+}
+
+impl<Ctx> BitEncode<Ctx> for SocketAddrV4 {
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        self.ip().encode::<_, E>(write, ctx, ())?;
+        self.port().encode::<_, E>(write, ctx, ())
+    }
+}
+
+impl<Ctx> BitDecode<Ctx> for SocketAddrV6 {
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let ip = Ipv6Addr::decode::<_, E>(read, ctx, ())?;
+        let port = u16::decode::<_, E>(read, ctx, ())?;
+        let flowinfo = u32::decode::<_, E>(read, ctx, ())?;
+        let scope_id = u32::decode::<_, E>(read, ctx, ())?;
+        Ok(Self::new(ip, port, flowinfo, scope_id))
+    }
+}
+
+impl<Ctx> BitEncode<Ctx> for SocketAddrV6 {
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        self.ip().encode::<_, E>(write, ctx, ())?;
+        self.port().encode::<_, E>(write, ctx, ())?;
+        self.flowinfo().encode::<_, E>(write, ctx, ())?;
+        self.scope_id().encode::<_, E>(write, ctx, ())
+    }
+}
+
+impl<Tag, Ctx> BitDecode<Ctx, crate::Tag<Tag>> for SocketAddr
+where
+    Tag: TryInto<u8>,
+{
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, tag: crate::Tag<Tag>) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        match tag.0.try_into().map_err(|_| Error::TagConvert)? {
+            4 => SocketAddrV4::decode::<_, E>(read, ctx, ()).map(Self::V4),
+            6 => SocketAddrV6::decode::<_, E>(read, ctx, ()).map(Self::V6),
+            _ => Err(Error::Discriminant),
+        }
+    }
+}
+
+impl<Ctx> BitEncode<Ctx, Untagged> for SocketAddr {
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, _: Untagged) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        match self {
+            Self::V4(addr) => addr.encode::<_, E>(write, ctx, ()),
+            Self::V6(addr) => addr.encode::<_, E>(write, ctx, ()),
+        }
+    }
+}
+
+#[cfg(feature = "prepend-tags")]
+impl<Ctx> BitEncode<Ctx> for SocketAddr {
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        let family: u8 = if self.is_ipv4() { 4 } else { 6 };
+        family.encode::<_, E>(write, ctx, ())?;
+        self.encode::<_, E>(write, ctx, Untagged)
+    }
+}
+
+#[cfg(feature = "prepend-tags")]
+impl<Ctx> BitDecode<Ctx> for SocketAddr {
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let tag = u8::decode::<_, E>(read, ctx, ())?;
+        Self::decode::<_, E>(read, ctx, crate::Tag(tag))
+    }
+}
+
+#[cfg(test)]
+mod v4 {
+    use super::*;
+
+    test_codec!(SocketAddrV4; SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 0), 80) => [192, 168, 1, 0, 0, 80]);
+    test_roundtrip!(SocketAddrV4);
+}
+
+#[cfg(test)]
+mod v6 {
+    use super::*;
+
+    test_codec!(SocketAddrV6;
+        SocketAddrV6::new(Ipv6Addr::LOCALHOST, 80, 1, 2) =>
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 80, 0, 0, 0, 1, 0, 0, 0, 2]
+    );
+    test_roundtrip!(SocketAddrV6);
+}
+
+#[cfg(test)]
+mod tagged {
+    use crate::Tag;
+
+    use super::*;
+
+    test_codec!(SocketAddr| Untagged, Tag(4);
+        SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 443)) =>
+        [10, 0, 0, 1, 0x01, 0xbb]
+    );
+
+    #[test]
+    fn unknown_family() {
+        assert!(matches!(
+            <SocketAddr as BitDecode<(), Tag<u8>>>::decode::<_, bitstream_io::BigEndian>(
+                &mut bitstream_io::BitReader::endian([0u8; 6].as_slice(), bitstream_io::BigEndian),
+                &mut (),
+                Tag(5),
+            ),
+            Err(Error::Discriminant)
+        ));
+    }
+
+    #[cfg(feature = "prepend-tags")]
+    test_roundtrip!(SocketAddr);
+}
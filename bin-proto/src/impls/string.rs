@@ -1,6 +1,6 @@
 #![cfg(feature = "alloc")]
 
-use crate::{util, BitDecode, BitEncode, Error, Result, Untagged};
+use crate::{limits, util, BitDecode, BitEncode, Error, Result, Untagged};
 
 use alloc::{string::String, vec::Vec};
 use bitstream_io::{BitRead, BitWrite, Endianness};
@@ -15,7 +15,8 @@ where
         E: Endianness,
     {
         let item_count = tag.0.try_into().map_err(|_| Error::TagConvert)?;
-        let mut bytes = Vec::with_capacity(item_count);
+        limits::reserve::<u8>(0, item_count)?;
+        let mut bytes = Vec::with_capacity(limits::capacity::<u8>(item_count));
         for _ in 0..item_count {
             bytes.push(u8::decode::<_, E>(read, ctx, ())?);
         }
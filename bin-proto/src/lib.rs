@@ -79,9 +79,12 @@
 //! - `std` — Enables support for types in the standard library.
 //! - `alloc` — Enables support for types in the `alloc` crate.
 //! - `derive` — Provides procedural macros for deriving traits [`BitEncode`] and [`BitDecode`].
-//! - `prepend-tags` — Enables tag prepending for common types ([`Option`], [`str`], etc.), removing
-//!   the need for explicit tag specification for encoding/decoding. **WARNING**: length tags are
-//!   encoded as `usize`, meaning they may vary if targets have different pointer widths.
+//! - `prepend-tags` — Enables tag prepending for common types ([`Option`],
+//!   [`Result`](core::result::Result), [`str`], etc.), removing the need for explicit tag
+//!   specification for encoding/decoding. **WARNING**: length tags are encoded as `usize`, meaning
+//!   they may vary if targets have different pointer widths.
+//! - `bitflags` — Provides `impl_bitflags!` for implementing [`BitEncode`] and [`BitDecode`] for
+//!   types generated by the [`bitflags`](https://docs.rs/bitflags) crate.
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![cfg_attr(docsrs, feature(rustdoc_internals))]
@@ -100,6 +103,7 @@
     clippy::style
 )]
 #![allow(clippy::module_name_repetitions, clippy::missing_errors_doc)]
+#![cfg_attr(test, allow(clippy::unwrap_used))]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
@@ -108,8 +112,13 @@ extern crate std;
 
 pub use self::codec::BitCodec;
 pub use self::codec::{BitDecode, BitDecodeExt, BitEncode, BitEncodeExt};
-pub use self::discriminable::Discriminable;
+pub use self::discriminable::{Discriminable, KnownDiscriminant};
+pub use self::dyn_endian::DynEndian;
+#[cfg(feature = "alloc")]
+pub use self::dynamic::Describe;
 pub use self::error::{Error, Result};
+#[cfg(feature = "alloc")]
+pub use self::tlv::{Tlv, UnknownValue};
 pub use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
 
 /// Derive the [`BitDecode`] and [`BitEncode`] traits.
@@ -131,6 +140,12 @@ pub use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
 ///     #[field]
 ///     field: Type,
 /// }
+///
+/// #[container, union]
+/// union Union {
+///     #[variant]
+///     field: Type,
+/// }
 /// ```
 ///
 /// # Using Context
@@ -158,28 +173,88 @@ pub use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
 /// # }
 /// ```
 ///
+/// # Unions
+///
+/// Unions are decoded with a [`Tag`] containing the [`discriminant`](#discriminant) of the field
+/// to be decoded, like enums. Since the active field of a union is unknown, encoding requires a
+/// [`Selector`] containing the discriminant of the active field, which can be passed using
+/// [`encode_tag`](#encode_tag).
+///
+/// The derived [`BitEncode`] reads the selected field in an `unsafe` block, so deriving it for a
+/// union fails in crates with `#![forbid(unsafe_code)]`.
+///
+/// ```
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// #[bin_proto(discriminant_type = u8)]
+/// union Value {
+///     #[bin_proto(discriminant = 0)]
+///     int: u32,
+///     #[bin_proto(discriminant = 1)]
+///     float: f32,
+/// }
+///
+/// #[derive(BitDecode, BitEncode)]
+/// struct Tagged {
+///     kind: u8,
+///     #[bin_proto(tag = kind)]
+///     // SAFETY: `kind` is the discriminant of the active field of `value`.
+///     #[bin_proto(encode_tag = unsafe { bin_proto::Selector::new(self.kind) })]
+///     value: Value,
+/// }
+/// ```
+///
 /// # Attributes
 ///
 /// | Attribute | Scope | Applicability |
 /// |-|-|-|
-/// | [`discriminant_type`](#discriminant_type) | enum | rw |
+/// | [`discriminant_type`](#discriminant_type) | enum, union | rw |
 /// | [`discriminant`](#discriminant) | variant | rw |
 /// | [`other`](#other) | variant | r |
 /// | [`bits`](#bits) | field, enum | rw |
 /// | [`untagged`](#untagged) | field | rw |
 /// | [`tag`](#tag) | field | rw |
 /// | [`tag_type`](#tag_type) | field | rw |
+/// | [`until`](#until) | field | rw |
+/// | [`terminator`](#terminator) | field | rw |
+/// | [`include_terminator`](#include_terminator) | field | rw |
+/// | [`write_terminator`](#write_terminator) | field | w |
+/// | [`item_tag`](#item_tag) | field | rw |
 /// | [`write_value`](#write_value) | field | w |
-/// | [`ctx`](#ctx) | container | rw |
+/// | [`encode_tag`](#encode_tag) | field | w |
+/// | [`ctx`](#ctx) | container, field | rw |
 /// | [`ctx_bounds`](#ctx_bounds) | container | rw |
 /// | [`skip_encode`](#skip_encode) | field, variant | w |
 /// | [`skip_decode`](#skip_decode) | field, variant | r |
 /// | [`skip`](#skip) | field, variant | rw |
+/// | [`default`](#default) | field | r |
 /// | [`pad_before`](#pad_before) | field, struct | rw |
 /// | [`pad_after`](#pad_after) | field, struct | rw |
+/// | [`pad_value`](#pad_value) | field, struct | rw |
+/// | [`pad_with`](#pad_with) | field, struct | rw |
+/// | [`strict`](#strict) | field, struct, enum | r |
 /// | [`magic`](#magic) | field, struct | rw |
+/// | [`bit_order`](#bit_order) | struct | rw |
+/// | [`endian_from`](#endian_from) | field | rw |
+/// | [`implicit`](#implicit) | field | rw |
+/// | [`explicit`](#explicit) | field | rw |
+/// | [`format`](#format) | struct | rw |
+/// | [`field_number`](#field_number) | field | rw |
+/// | [`wire_type`](#wire_type) | field | rw |
+/// | [`map`](#map) | field | r |
+/// | [`try_map`](#try_map) | field | r |
+/// | [`map_back`](#map_back) | field | w |
+/// | [`try_map_back`](#try_map_back) | field | w |
+/// | [`with`](#with) | field | rw |
+/// | [`assert`](#assert) | field, struct | r |
+/// | [`after_decode`](#after_decode) | field, struct, enum | r |
+/// | [`before_encode`](#before_encode) | field, struct, enum | w |
+/// | [`recursive`](#recursive) | struct, enum | r |
 /// | [`crate`](#crate) | struct, enum | rw |
 ///
+/// Expressions in attributes refer to fields of the parent container by name. Unnamed fields of
+/// tuple structs and tuple variants are named `field_0`, `field_1`, etc.
+///
 /// ## `discriminant_type`
 /// `#[bin_proto(discriminant_type = <type>)]`
 /// - `<type>`: an arbitrary type that implements [`BitDecode`] or [`BitEncode`]
@@ -251,7 +326,7 @@ pub use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
 ///
 /// **WARNING**: Bitfields disregard endianness and instead have the same endianness as the
 /// underlying [`BitRead`] / [`BitWrite`] instance. If you're using bitfields, you almost always
-/// want a big endian stream.
+/// want a big endian stream, or to specify [`bit_order`](#bit_order).
 ///
 /// ```
 /// # use bin_proto::{BitDecode, BitEncode};
@@ -277,10 +352,11 @@ pub use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
 /// ## `tag`
 /// `#[bin_proto(tag = <expr>)]`
 /// - `<expr>`: arbitrary expression. Fields in parent container can be used without prefixing them
-///   with `self`.
+///   with `self`, with unnamed fields named `field_0`, `field_1`, etc.
 ///
 /// Specify tag of field. The tag represents a length prefix for variable-length fields, and a
-/// boolean for [`Option`].
+/// boolean for [`Option`] and [`Result`](core::result::Result), which is `true` for [`Some`] and
+/// [`Ok`].
 ///
 /// ```
 /// # #[cfg(feature = "alloc")]
@@ -302,8 +378,8 @@ pub use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
 /// - `<expr>`: arbitrary expression. Fields in parent container should be prefixed with `self`.
 ///
 /// Specify tag of field. The tag represents a length prefix for variable-length fields, and a
-/// boolean for [`Option`]. The tag is placed directly before the field. The `tag_value` only has
-/// to be specified when deriving [`BitEncode`].
+/// boolean for [`Option`] and [`Result`](core::result::Result). The tag is placed directly before
+/// the field. The `tag_value` only has to be specified when deriving [`BitEncode`].
 ///
 /// ```
 /// # #[cfg(feature = "alloc")]
@@ -317,6 +393,129 @@ pub use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
 /// # }
 /// ```
 ///
+/// ## `until`
+/// `#[bin_proto(until = <expr>)]`
+/// - `<expr>`: Must evaluate to a function `FnMut(&T) -> bool`, where `T` is the item type
+///
+/// Decode items until the function returns `true` for a decoded item, which is included in the
+/// collection. Items are encoded as if the field were [`untagged`](#untagged), so the final item
+/// must be present in the collection. See [`include_terminator`](#include_terminator) and
+/// [`write_terminator`](#write_terminator) to change either choice.
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// struct WithContinuationBits {
+///     #[bin_proto(until = |byte: &u8| byte & 0x80 == 0)]
+///     bytes: Vec<u8>,
+/// }
+/// # }
+/// ```
+///
+/// ## `terminator`
+/// `#[bin_proto(terminator = <expr>)]`
+/// - `<expr>`: An expression that can be coerced to the item type, and does not refer to other
+///   fields
+///
+/// Decode items until an item equal to the terminator is read, which is not included in the
+/// collection. The terminator is written after the items when encoding. See
+/// [`include_terminator`](#include_terminator) and [`write_terminator`](#write_terminator) to
+/// change either choice.
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// struct WithSentinel {
+///     #[bin_proto(terminator = 0xFF)]
+///     data: Vec<u8>,
+/// }
+/// # }
+/// ```
+///
+/// ## `include_terminator`
+/// `#[bin_proto(include_terminator[ = <bool>]?)]`
+///
+/// Combined with [`until`](#until) or [`terminator`](#terminator), sets whether the item that
+/// ends the collection is included in it, as its last item. Defaults to `true` for `until` and
+/// `false` for `terminator`.
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// struct WithIncludedSentinel {
+///     #[bin_proto(terminator = 0xFF, include_terminator)]
+///     data: Vec<u8>,
+/// }
+/// # }
+/// ```
+///
+/// ## `write_terminator`
+/// `#[bin_proto(write_terminator[ = <bool>]?)]`
+///
+/// Combined with [`until`](#until) or [`terminator`](#terminator), sets whether the item that
+/// ends the collection is written when encoding. An included terminator is the last item of the
+/// collection, so it must be written. An excluded terminator is written after the items, unless
+/// this is `false`, in which case it is still read when decoding, so the value only round-trips
+/// if the terminator is written by other means. Defaults to `true`, except for `until` with
+/// `include_terminator = false`, where the terminator can't be written.
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// # use bin_proto::{BitCodec, BitDecode, BitEncode};
+/// #[derive(Debug, BitDecode, BitEncode, PartialEq)]
+/// struct WithWrittenTerminator {
+///     #[bin_proto(terminator = 0, include_terminator, write_terminator)]
+///     data: Vec<u8>,
+///     checksum: u8,
+/// }
+///
+/// let value = WithWrittenTerminator {
+///     data: vec![1, 2, 0],
+///     checksum: 3,
+/// };
+/// let bytes = value.encode_bytes(bin_proto::BigEndian).unwrap();
+/// assert_eq!(bytes, [1, 2, 0, 3]);
+/// assert_eq!(
+///     WithWrittenTerminator::decode_bytes(&bytes, bin_proto::BigEndian).unwrap(),
+///     (value, 32)
+/// );
+/// # }
+/// ```
+///
+/// ## `item_tag`
+/// `#[bin_proto(item_tag = <expr>)]`
+/// - `<expr>`: An expression whose value is wrapped in [`Tag`] and passed to each item when
+///   decoding, so its type must be a tag accepted by the item type, such as a length for
+///   collections. The index of the item is available as `__index`, and fields in parent container
+///   that have already been decoded can be used without prefixing them with `self`.
+///
+/// Decode each item of a collection with the tag [`Tag(<expr>)`](Tag), and encode it as
+/// [`Untagged`]. Must be combined with [`tag`](#tag), [`tag_type`](#tag_type), or
+/// [`untagged`](#untagged), which determine the number of items, and cannot be combined with
+/// [`bits`](#bits), [`until`](#until), or [`terminator`](#terminator).
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// struct Records {
+///     count: u8,
+///     #[bin_proto(tag = count as usize)]
+///     lens: Vec<u8>,
+///     #[bin_proto(tag = count as usize, item_tag = lens[__index] as usize)]
+///     records: Vec<Vec<u8>>,
+/// }
+/// # }
+/// ```
+///
 /// ## `write_value`
 /// `#[bin_proto(write_value = <expr>)]`
 /// - `<expr>`: An expression that can be coerced to the field type. Fields in parent container
@@ -339,6 +538,34 @@ pub use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
 /// # }
 /// ```
 ///
+/// ## `encode_tag`
+/// `#[bin_proto(encode_tag = <expr>)]`
+/// - `<expr>`: An expression evaluating to the tag. Fields in parent container should be prefixed
+///   with `self`.
+///
+/// Specify the tag to be passed when encoding the field, instead of [`Untagged`]. Cannot be
+/// combined with [`tag_type`](#tag_type), [`bits`](#bits), [`implicit`](#implicit), or
+/// [`explicit`](#explicit), which determine the tag themselves.
+///
+/// ```
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// #[bin_proto(discriminant_type = u8)]
+/// union Value {
+///     #[bin_proto(discriminant = 0)]
+///     int: u32,
+///     #[bin_proto(discriminant = 1)]
+///     float: f32,
+/// }
+///
+/// #[derive(BitEncode)]
+/// struct Float {
+///     // SAFETY: The float field is always active.
+///     #[bin_proto(encode_tag = unsafe { bin_proto::Selector::new(1) })]
+///     value: Value,
+/// }
+/// ```
+///
 /// ## `ctx`
 /// `#[bin_proto(ctx = <type>)[, ctx_generics(<generic>[, <generic>]*)]?]`
 /// - `<type>`: The type of the context. Either a concrete type, or one of the container's generics
@@ -413,6 +640,30 @@ pub use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
 /// struct WithCtx;
 /// ```
 ///
+/// `#[bin_proto(ctx = <expr>)]`
+/// - `<expr>`: Must evaluate to `&mut <type>`, where `<type>` is the context of the field. The
+///   container's context is available as `__ctx`, and fields in parent container that have already
+///   been decoded can be used without prefixing them with `self` if only [`BitDecode`] is derived.
+///
+/// If applied to a field, pass a different context to the field.
+///
+/// ```
+/// # use bin_proto::{BitDecode, BitEncode};
+/// struct Inner;
+///
+/// struct Outer {
+///     inner: Inner,
+/// }
+///
+/// #[derive(BitDecode, BitEncode)]
+/// #[bin_proto(ctx = Inner)]
+/// struct NeedsInner;
+///
+/// #[derive(BitDecode, BitEncode)]
+/// #[bin_proto(ctx = Outer)]
+/// struct WithOuter(#[bin_proto(ctx = &mut __ctx.inner)] NeedsInner);
+/// ```
+///
 /// ## `ctx_bounds`
 /// `#[bin_proto(ctx_bounds(<bound>[, <bound>]*)[, ctx_generics(<generic>[, <generic>]*)]?)]`
 /// - `<bounds>`: Trait bounds that must be satisfied by the context
@@ -535,10 +786,30 @@ pub use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
 /// }
 /// ```
 ///
+/// ## `default`
+/// `#[bin_proto(default = <expr>)]`
+/// - `<expr>`: An expression that can be coerced to the field type. Fields in parent container
+///   that have already been decoded, and the context `__ctx`, can be used without prefixing them
+///   with `self`.
+///
+/// Use the expression instead of [`Default::default`] as the value of a field with
+/// [`skip_decode`](#skip_decode) or [`skip`](#skip).
+///
+/// ```
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// struct Header {
+///     total_length: u16,
+///     #[bin_proto(skip, default = total_length / 4)]
+///     payload_len_words: u16,
+/// }
+/// ```
+///
 /// ## `pad_before`
 /// `#[bin_proto(pad_before = <expr>)]`
 ///
-/// Insert 0 bits when writing and skip bits when reading, prior to processing the field.
+/// Insert 0 bits (or [`pad_value`](#pad_value)/[`pad_with`](#pad_with)) when writing and skip
+/// bits when reading, prior to processing the field.
 ///
 /// ```
 /// # use bin_proto::{BitDecode, BitEncode};
@@ -549,7 +820,8 @@ pub use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
 /// ## `pad_after`
 /// `#[bin_proto(pad_after = <expr>)]`
 ///
-/// Insert 0 bits when writing and skip bits when reading, after processing the field.
+/// Insert 0 bits (or [`pad_value`](#pad_value)/[`pad_with`](#pad_with)) when writing and skip
+/// bits when reading, after processing the field.
 ///
 /// ```
 /// # use bin_proto::{BitDecode, BitEncode};
@@ -557,6 +829,70 @@ pub use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
 /// struct Struct(#[bin_proto(pad_after = 3)] u8);
 /// ```
 ///
+/// ## `pad_value`
+/// `#[bin_proto(pad_value = <expr>)]`
+/// - `<expr>`: A `u8` repeated for every byte of padding, starting with its most significant bit,
+///   or with its least significant bit in a struct with [`bit_order = lsb0`](#bit_order)
+///
+/// Fill the padding of [`pad_before`](#pad_before) and [`pad_after`](#pad_after), and the alignment
+/// padding of [`bit_order`](#bit_order), with `<expr>` when writing, and expect it when reading in
+/// [`strict`](#strict) mode. Applies to all fields of a struct if specified on it, unless a field
+/// specifies its own `pad_value` or [`pad_with`](#pad_with).
+///
+/// ```
+/// # use bin_proto::{BitCodec, BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// struct Struct(#[bin_proto(pad_after = 12, pad_value = 0xff)] u8);
+///
+/// assert_eq!(
+///     Struct(1).encode_bytes(bin_proto::BigEndian).unwrap(),
+///     vec![0x01, 0xff, 0xf0]
+/// );
+/// ```
+///
+/// ## `pad_with`
+/// `#[bin_proto(pad_with = <expr>)]`
+/// - `<expr>`: A byte pattern implementing `AsRef<[u8]>`, repeated for the length of the padding
+///
+/// Like [`pad_value`](#pad_value), but with a multi-byte pattern.
+///
+/// ```
+/// # use bin_proto::{BitCodec, BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// #[bin_proto(pad_after = 24, pad_with = [0xaa, 0x55])]
+/// struct Frame(u8);
+///
+/// assert_eq!(
+///     Frame(1).encode_bytes(bin_proto::BigEndian).unwrap(),
+///     vec![0x01, 0xaa, 0x55, 0xaa]
+/// );
+/// ```
+///
+/// ## `strict`
+/// `#[bin_proto(strict)]`
+///
+/// Verify that padding read from [`pad_before`](#pad_before), [`pad_after`](#pad_after) and the
+/// alignment of [`bit_order`](#bit_order) matches [`pad_value`](#pad_value) or
+/// [`pad_with`](#pad_with), or 0 bits if unspecified, returning [`Error::Padding`] with the
+/// offset of the first mismatching bit within the padding otherwise. Applies to all fields of a
+/// struct or enum if specified on it.
+///
+/// ```
+/// # use bin_proto::{BitCodec, BitDecode, BitEncode, Error};
+/// #[derive(Debug, BitDecode, BitEncode)]
+/// #[bin_proto(strict)]
+/// struct Header {
+///     #[bin_proto(bits = 5, pad_after = 3)]
+///     version: u8,
+/// }
+///
+/// assert!(Header::decode_bytes(&[0b0000_1000], bin_proto::BigEndian).is_ok());
+/// assert!(matches!(
+///     Header::decode_bytes(&[0b0000_1010], bin_proto::BigEndian),
+///     Err(Error::Padding { offset: 1 })
+/// ));
+/// ```
+///
 /// ## `magic`
 /// `#[bin_proto(magic = <expr>)]`
 /// - `<expr>`: Must evaluate to `&[u8; _]`
@@ -570,6 +906,324 @@ pub use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
 /// struct Magic(#[bin_proto(magic = b"123")] u8);
 /// ```
 ///
+/// ## `bit_order`
+/// `#[bin_proto(bit_order = <order>)]`
+/// - `<order>`: `lsb0` to pack bitfields starting at the least significant bit of each byte, or
+///   `msb0` to pack them starting at the most significant bit
+///
+/// Pack the struct's bitfields in the specified order, independently of the endianness of
+/// integers and of the underlying [`BitRead`] / [`BitWrite`] instance. The struct must start on a
+/// byte boundary, or decoding and encoding return [`Error::Unaligned`], and is padded to a whole
+/// number of bytes.
+///
+/// ```
+/// # use bin_proto::{BitCodec, BitDecode, BitEncode};
+/// #[derive(Debug, BitDecode, BitEncode, PartialEq)]
+/// #[bin_proto(bit_order = lsb0)]
+/// struct Header {
+///     #[bin_proto(bits = 1)]
+///     last: bool,
+///     #[bin_proto(bits = 2)]
+///     kind: u8,
+///     #[bin_proto(bits = 5)]
+///     reserved: u8,
+/// }
+///
+/// assert_eq!(
+///     Header::decode_bytes(&[0b0000_0101], bin_proto::BigEndian).unwrap().0,
+///     Header { last: true, kind: 2, reserved: 0 }
+/// );
+/// ```
+///
+/// ## `endian_from`
+/// `#[bin_proto(endian_from = <expr>)]`
+/// - `<expr>`: An expression that can be converted into a [`DynEndian`] with [`From`]
+///
+/// Decode and encode the field with the endianness selected by `<expr>` at runtime, instead of the
+/// endianness of the enclosing codec. The expression can reference previous fields, which are
+/// values when decoding and references when encoding. [`DynEndian`] can be converted from a
+/// reference to any [`Copy`] type that converts into it.
+///
+/// ```
+/// # use bin_proto::{BitCodec, BitDecode, BitEncode, DynEndian};
+/// #[derive(Debug, Clone, Copy, BitDecode, BitEncode, PartialEq)]
+/// #[bin_proto(discriminant_type = u16)]
+/// enum ByteOrder {
+///     #[bin_proto(discriminant = 0x4949)]
+///     Little,
+///     #[bin_proto(discriminant = 0x4d4d)]
+///     Big,
+/// }
+///
+/// impl From<ByteOrder> for DynEndian {
+///     fn from(value: ByteOrder) -> Self {
+///         match value {
+///             ByteOrder::Little => DynEndian::Little,
+///             ByteOrder::Big => DynEndian::Big,
+///         }
+///     }
+/// }
+///
+/// #[derive(Debug, BitDecode, BitEncode, PartialEq)]
+/// struct Header {
+///     byte_order: ByteOrder,
+///     #[bin_proto(endian_from = byte_order)]
+///     version: u16,
+/// }
+///
+/// assert_eq!(
+///     Header::decode_bytes(b"II\x2a\x00", bin_proto::BigEndian).unwrap().0,
+///     Header { byte_order: ByteOrder::Little, version: 42 }
+/// );
+/// ```
+///
+/// ## `implicit`
+/// `#[bin_proto(implicit = <expr>)]`
+/// - `<expr>`: A [`u32`] tag number
+///
+/// Decode and encode the field as ASN.1 BER with the tag `[<expr>] IMPLICIT`, replacing the
+/// universal tag of the field's [`ber::Universal`] type with a context-specific one. See
+/// [`ber::Implicit`].
+///
+/// ```
+/// # use bin_proto::{BitCodec, BitDecode, BitEncode, ber::Integer};
+/// #[derive(Debug, BitDecode, BitEncode, PartialEq)]
+/// struct Version(#[bin_proto(implicit = 2)] Integer);
+///
+/// assert_eq!(
+///     Version::decode_bytes(&[0x82, 0x01, 0x03], bin_proto::BigEndian).unwrap().0,
+///     Version(Integer(3))
+/// );
+/// ```
+///
+/// ## `explicit`
+/// `#[bin_proto(explicit = <expr>)]`
+/// - `<expr>`: A [`u32`] tag number
+///
+/// Decode and encode the field as ASN.1 BER with the tag `[<expr>] EXPLICIT`, wrapping the field's
+/// encoding in a constructed, context-specific value. See [`ber::Explicit`].
+///
+/// ```
+/// # use bin_proto::{BitCodec, BitDecode, BitEncode, ber::Integer};
+/// #[derive(Debug, BitDecode, BitEncode, PartialEq)]
+/// struct Version(#[bin_proto(explicit = 0)] Integer);
+///
+/// assert_eq!(
+///     Version::decode_bytes(&[0xa0, 0x03, 0x02, 0x01, 0x03], bin_proto::BigEndian).unwrap().0,
+///     Version(Integer(3))
+/// );
+/// ```
+///
+/// ## `format`
+/// `#[bin_proto(format = protobuf)]`
+///
+/// Decode and encode the struct as a Protocol Buffers message, with fields numbered by
+/// [`field_number`](#field_number). See [`protobuf`]. Magic, padding and `bit_order` cannot be
+/// used on the struct.
+///
+/// ## `field_number`
+/// `#[bin_proto(field_number = <expr>)]`
+/// - `<expr>`: A [`u32`] field number
+///
+/// The number of a field of a struct with the [`format = protobuf`](#format) attribute. Other
+/// attributes of the field, apart from [`skip`](#skip) and [`default`](#default), cannot be used.
+///
+/// ```
+/// # use bin_proto::{BitCodec, BitDecode, BitEncode};
+/// #[derive(Debug, BitDecode, BitEncode, PartialEq)]
+/// #[bin_proto(format = protobuf)]
+/// struct Request {
+///     #[bin_proto(field_number = 1)]
+///     query: String,
+///     #[bin_proto(field_number = 3)]
+///     page: u32,
+/// }
+///
+/// assert_eq!(
+///     Request::decode_bytes(&[0x18, 0x02, 0x0a, 0x01, b'q'], bin_proto::BigEndian).unwrap().0,
+///     Request { query: "q".into(), page: 2 }
+/// );
+/// ```
+///
+/// ## `wire_type`
+/// `#[bin_proto(wire_type = <type>)]`
+///
+/// Decode and encode the field as `<type>`, converting between the two with
+/// [`map`](#map)/[`try_map`](#try_map) and [`map_back`](#map_back)/[`try_map_back`](#try_map_back).
+/// Any tag or bit-width applies to the wire type.
+///
+/// ## `map`
+/// `#[bin_proto(wire_type = <type>, map = <expr>)]`
+/// - `<expr>`: Must evaluate to a function `Fn(<type>) -> T`, where `T` is the field type
+///
+/// Convert the decoded wire value into the field's value.
+///
+/// ## `try_map`
+/// `#[bin_proto(wire_type = <type>, try_map = <expr>)]`
+/// - `<expr>`: Must evaluate to a function `Fn(<type>) -> Result<T, E>`, where `T` is the field
+///   type and [`Error`] implements `From<E>`
+///
+/// Fallibly convert the decoded wire value into the field's value.
+///
+/// ## `map_back`
+/// `#[bin_proto(wire_type = <type>, map_back = <expr>)]`
+/// - `<expr>`: Must evaluate to a function `Fn(&T) -> <type>`, where `T` is the field type
+///
+/// Convert the field's value into the wire value to be encoded.
+///
+/// ```
+/// # use bin_proto::{BitDecode, BitEncode};
+/// # use core::time::Duration;
+/// #[derive(BitDecode, BitEncode)]
+/// struct Timeout {
+///     #[bin_proto(
+///         wire_type = u8,
+///         map = |secs: u8| Duration::from_secs(secs.into()),
+///         try_map_back = |duration: &Duration| u8::try_from(duration.as_secs()),
+///     )]
+///     duration: Duration,
+/// }
+/// ```
+///
+/// ## `try_map_back`
+/// `#[bin_proto(wire_type = <type>, try_map_back = <expr>)]`
+/// - `<expr>`: Must evaluate to a function `Fn(&T) -> Result<<type>, E>`, where `T` is the field
+///   type and [`Error`] implements `From<E>`
+///
+/// Fallibly convert the field's value into the wire value to be encoded.
+///
+/// ## `with`
+/// `#[bin_proto(with = <path>)]`
+/// - `<path>`: A module containing `decode` and `encode` functions with the same signatures as
+///   [`BitDecode::decode`] and [`BitEncode::encode`], taking the value as the first argument of
+///   `encode`
+///
+/// Use the functions in the specified module to decode and encode the field.
+///
+/// ```
+/// # use bin_proto::{BitDecode, BitEncode};
+/// mod yes_no {
+///     use bin_proto::{
+///         bitstream_io::{BitRead, BitWrite, Endianness},
+///         BitDecode, BitEncode, Result,
+///     };
+///
+///     pub fn decode<R: BitRead, E: Endianness>(
+///         read: &mut R,
+///         ctx: &mut impl Sized,
+///         tag: (),
+///     ) -> Result<bool> {
+///         Ok(u8::decode::<_, E>(read, ctx, tag)? == b'Y')
+///     }
+///
+///     pub fn encode<W: BitWrite, E: Endianness>(
+///         value: &bool,
+///         write: &mut W,
+///         ctx: &mut impl Sized,
+///         tag: (),
+///     ) -> Result<()> {
+///         if *value { b'Y' } else { b'N' }.encode::<_, E>(write, ctx, tag)
+///     }
+/// }
+///
+/// #[derive(BitDecode, BitEncode)]
+/// struct Answer(#[bin_proto(with = yes_no)] bool);
+/// ```
+///
+/// ## `assert`
+/// `#[bin_proto(assert = <expr>)]`
+/// - `<expr>`: A boolean expression. Fields in parent container that have already been decoded can
+///   be used without prefixing them with `self`, with unnamed fields named `field_0`, `field_1`,
+///   etc.
+///
+/// Return [`Error::Validation`] if the expression evaluates to `false` after decoding the field or
+/// struct.
+///
+/// ```
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// #[bin_proto(assert = min <= max)]
+/// struct Range {
+///     #[bin_proto(assert = min != 0)]
+///     min: u8,
+///     max: u8,
+/// }
+///
+/// #[derive(BitDecode, BitEncode)]
+/// struct Reserved(#[bin_proto(bits = 4, assert = field_0 == 0)] u8, #[bin_proto(bits = 4)] u8);
+/// ```
+///
+/// ## `after_decode`
+/// `#[bin_proto(after_decode = <expr>)]`
+/// - `<expr>`: An expression evaluating to `()`. Fields in parent container that have already been
+///   decoded, and the context `__ctx`, can be used without prefixing them with `self`. On an enum,
+///   the decoded value is available as `__value`.
+///
+/// Evaluate the expression after decoding the field, struct or enum, e.g. to update the context.
+///
+/// ```
+/// # use bin_proto::{BitDecode, BitEncode};
+/// struct Session {
+///     version: u8,
+/// }
+///
+/// #[derive(BitDecode, BitEncode)]
+/// #[bin_proto(ctx = Session)]
+/// struct Hello {
+///     #[bin_proto(after_decode = __ctx.version = version)]
+///     version: u8,
+/// }
+/// ```
+///
+/// ## `before_encode`
+/// `#[bin_proto(before_encode = <expr>)]`
+/// - `<expr>`: An expression evaluating to `()`. Fields in parent container should be prefixed
+///   with `self`, and the context is available as `__ctx`.
+///
+/// Evaluate the expression before encoding the field, struct or enum, e.g. to update the context.
+/// On an enum, it is evaluated before the discriminant is encoded.
+///
+/// ```
+/// # use bin_proto::{BitDecode, BitEncode};
+/// struct Session {
+///     version: u8,
+/// }
+///
+/// #[derive(BitDecode, BitEncode)]
+/// #[bin_proto(ctx = Session)]
+/// struct Hello {
+///     #[bin_proto(before_encode = __ctx.version = self.version)]
+///     version: u8,
+/// }
+/// ```
+///
+/// ## `recursive`
+/// `#[bin_proto(recursive)]`
+///
+/// Count the struct or enum towards [`Limits::max_depth`](limits::Limits::max_depth) when
+/// decoding. Types with fields whose types name the type itself, or `Self`, are counted
+/// automatically. Mutually recursive types, and types that recur through a type alias, must be
+/// marked explicitly.
+///
+/// ```
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// #[bin_proto(discriminant_type = u8, recursive)]
+/// enum Value {
+///     #[bin_proto(discriminant = 0)]
+///     Number(u8),
+///     #[bin_proto(discriminant = 1)]
+///     List(List),
+/// }
+///
+/// #[derive(BitDecode, BitEncode)]
+/// #[bin_proto(recursive)]
+/// struct List {
+///     #[bin_proto(tag_type = u8, tag_value = self.items.len() as u8)]
+///     items: Vec<Value>,
+/// }
+/// ```
+///
 /// ## `crate`
 /// `#[bin_proto(crate = <path>)]`
 ///
@@ -587,16 +1241,41 @@ pub use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
 #[cfg(feature = "derive")]
 pub use bin_proto_derive::{BitDecode, BitEncode};
 
+/// Derive the [`dynamic::Describe`] trait, exporting a [`dynamic::Schema`] of a type from the same
+/// attributes as [`macro@BitDecode`] and [`macro@BitEncode`].
+///
+/// Attributes that a [`dynamic::Schema`] can't express are compile errors. These are `bit_order`,
+/// `endian_from`, `with`, `pad_value`, `pad_with`, `item_tag`, `until`, `terminator`, `implicit`,
+/// `explicit`, `other` variants, and only one of `skip_encode` and `skip_decode`. A `tag` must be a
+/// previous field, optionally followed by an `as` cast.
+#[cfg(all(feature = "derive", feature = "alloc"))]
+pub use bin_proto_derive::Describe;
+
 #[macro_use]
 mod codec;
 
+#[cfg(feature = "alloc")]
+pub mod ber;
 mod discriminable;
+mod dyn_endian;
+#[cfg(feature = "alloc")]
+pub mod dynamic;
 mod error;
 mod impls;
+pub mod limits;
+#[cfg(feature = "alloc")]
+pub mod protobuf;
+#[cfg(feature = "alloc")]
+pub mod text;
+#[cfg(feature = "alloc")]
+mod tlv;
 pub mod util;
 
 pub extern crate bitstream_io;
 
+#[cfg(feature = "bitflags")]
+pub extern crate bitflags;
+
 /// A marker for [`BitEncode`] implementors that don't prepend their tag, and [`BitDecode`]
 /// implementors that usually have a tag, but can be read to EOF
 pub struct Untagged;
@@ -607,6 +1286,63 @@ pub struct Tag<T>(pub T);
 /// A marker for [`BitDecode`] and [`BitEncode`] implementors that support bitfield operations.
 pub struct Bits<const C: u32>;
 
+/// A marker for [`BitDecode`] and [`BitEncode`] implementors that are UTF-8 encoded, such as
+/// [`char`].
+pub struct Utf8;
+
+/// A marker for [`BitDecode`] implementors that read items until the predicate returns `true` for
+/// a decoded item, which is included in the result.
+pub struct Until<F>(pub F);
+
+/// A marker for [`BitDecode`] and [`BitEncode`] implementors that are terminated by a sentinel
+/// item, which is not included in the result, but is written after the items.
+pub struct Terminated<T>(pub T);
+
+/// A marker for [`BitDecode`] and [`BitEncode`] implementors that end at a delimiting item.
+///
+/// The delimiting item is found by [`Until`] or [`Terminated`], and whether it is included in the
+/// result and whether it is written when encoding are independent choices.
+///
+/// `Delimited { delimiter: Until(f), include: true, write: true }` behaves like [`Until(f)`](Until)
+/// and `Delimited { delimiter: Terminated(t), include: false, write: true }` like
+/// [`Terminated(t)`](Terminated).
+pub struct Delimited<D> {
+    /// The [`Until`] or [`Terminated`] that finds the delimiting item.
+    pub delimiter: D,
+    /// Whether the delimiting item is included in the result as its last item.
+    pub include: bool,
+    /// Whether the delimiting item is written when encoding. An included delimiting item is the
+    /// last item of the value, which is skipped if this isn't set, returning
+    /// [`Error::Delimiter`] if the last item isn't a delimiting item. Otherwise, only a
+    /// [`Terminated`] item can be written, after the items.
+    pub write: bool,
+}
+
+/// A marker for [`BitEncode`] implementors that require the caller to select which of their fields
+/// is active, such as derived unions.
+pub struct Selector<T>(T);
+
+impl<T> Selector<T> {
+    /// Creates a selector for the field identified by `value`.
+    ///
+    /// # Safety
+    ///
+    /// When encoding a union, `value` must be the discriminant of the union's active field. Reading
+    /// any other field is undefined behavior if its bytes are not a valid value of its type.
+    pub const unsafe fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the value identifying the selected field.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// A marker for [`BitDecode`] and [`BitEncode`] implementors that pass a tag to each item. The
+/// function receives the index of the item and returns its tag.
+pub struct TagItems<T, F>(pub T, pub F);
+
 /// ```compile_fail
 /// # use bin_proto::{BitDecode, BitEncode};
 /// #[derive(BitDecode, BitEncode)]
@@ -620,3 +1356,113 @@ pub struct Bits<const C: u32>;
 #[cfg(all(feature = "derive", feature = "alloc", doctest))]
 #[allow(unused)]
 fn compile_fail_if_multiple_exclusive_attrs() {}
+
+/// ```compile_fail
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// struct IncludedUnwrittenTerminator {
+///     #[bin_proto(until = |byte: &u8| *byte == 0, write_terminator = false)]
+///     pub data: Vec<u8>,
+/// }
+/// ```
+#[cfg(all(feature = "derive", feature = "alloc", doctest))]
+#[allow(unused)]
+fn compile_fail_if_included_terminator_not_written() {}
+
+/// ```compile_fail
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// struct ItemTagWithTerminator {
+///     #[bin_proto(terminator = Vec::new(), item_tag = 2)]
+///     pub records: Vec<Vec<u8>>,
+/// }
+/// ```
+#[cfg(all(feature = "derive", feature = "alloc", doctest))]
+#[allow(unused)]
+fn compile_fail_if_item_tag_with_terminator() {}
+
+/// ```compile_fail
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// #[bin_proto(discriminant_type = u8)]
+/// union Value {
+///     #[bin_proto(discriminant = 0)]
+///     int: u32,
+/// }
+///
+/// #[derive(BitEncode)]
+/// struct EncodeTagWithTagType {
+///     #[bin_proto(tag_type = u8, tag_value = 0)]
+///     #[bin_proto(encode_tag = unsafe { bin_proto::Selector::new(0) })]
+///     value: Value,
+/// }
+/// ```
+#[cfg(all(feature = "derive", doctest))]
+#[allow(unused)]
+fn compile_fail_if_encode_tag_with_tag_type() {}
+
+/// ```compile_fail
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// #[bin_proto(format = protobuf)]
+/// struct ProtobufBits {
+///     #[bin_proto(field_number = 1, bits = 3)]
+///     value: u32,
+/// }
+/// ```
+#[cfg(all(feature = "derive", feature = "alloc", doctest))]
+#[allow(unused)]
+fn compile_fail_if_protobuf_field_with_bits() {}
+
+/// ```compile_fail
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// #[bin_proto(format = protobuf, magic = b"\x01")]
+/// struct ProtobufMagic {
+///     #[bin_proto(field_number = 1)]
+///     value: u32,
+/// }
+/// ```
+#[cfg(all(feature = "derive", feature = "alloc", doctest))]
+#[allow(unused)]
+fn compile_fail_if_protobuf_with_magic() {}
+
+/// ```compile_fail
+/// # use bin_proto::{BitDecode, BitEncode};
+/// #[derive(BitDecode, BitEncode)]
+/// struct FieldNumberWithoutFormat {
+///     #[bin_proto(field_number = 1)]
+///     value: u32,
+/// }
+/// ```
+#[cfg(all(feature = "derive", doctest))]
+#[allow(unused)]
+fn compile_fail_if_field_number_without_format() {}
+
+/// ```compile_fail
+/// # use bin_proto::{BitDecode, Describe};
+/// #[derive(BitDecode, Describe)]
+/// struct DescribeComputedTag {
+///     count: u8,
+///     #[bin_proto(tag = count as usize * 2)]
+///     data: Vec<u8>,
+/// }
+/// ```
+#[cfg(all(feature = "derive", feature = "alloc", doctest))]
+#[allow(unused)]
+fn compile_fail_if_describe_computed_tag() {}
+
+/// ```compile_fail
+/// # use bin_proto::{BitDecode, Describe};
+/// #[derive(BitDecode, Describe)]
+/// #[bin_proto(discriminant_type = u8)]
+/// enum DescribeOther {
+///     #[bin_proto(discriminant = 0)]
+///     Zero,
+///     #[bin_proto(other)]
+///     Other,
+/// }
+/// ```
+#[cfg(all(feature = "derive", feature = "alloc", doctest))]
+#[allow(unused)]
+fn compile_fail_if_describe_other() {}
@@ -0,0 +1,280 @@
+//! Limits on the resources consumed while decoding untrusted input
+//!
+//! Limits are opt-in, and are only enforced within [`Limits::scope`]. Outside of any scope,
+//! [`reserve`] and [`enter`] return after checking a single atomic counter. With the `std`
+//! feature, limits are tracked per thread. Without it, they are tracked globally, so decoding
+//! concurrently on several threads or in interrupt handlers within a scope shares and can corrupt
+//! the counts. The preallocation of collections is bounded by [`capacity`] either way.
+
+use crate::{Error, Result};
+
+#[cfg(feature = "std")]
+use core::cell::Cell;
+use core::{
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The maximum number of bytes preallocated for a collection based on its tag. Collections with
+/// longer tags grow as their items are decoded.
+const MAX_PREALLOCATION: usize = 64 * 1024;
+
+/// Limits enforced while decoding within [`Limits::scope`].
+///
+/// ```
+/// # use bin_proto::{BitDecodeExt, Error, limits::{Limit, Limits}, Tag};
+/// let limits = Limits {
+///     max_items: 2,
+///     ..Limits::UNLIMITED
+/// };
+/// let decoded = limits.scope(|| {
+///     Vec::<u8>::decode_bytes_ctx(&[1, 2, 3], bin_proto::BigEndian, &mut (), Tag(3))
+/// });
+/// assert!(matches!(decoded, Err(Error::Limit(Limit::MaxItems))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Limits {
+    /// Maximum total size in bytes of the items decoded into collections.
+    pub max_bytes: usize,
+    /// Maximum number of items in a single collection.
+    pub max_items: usize,
+    /// Maximum nesting depth of recursive derived types, which are those with fields whose types
+    /// refer to the type itself, and those with the `recursive` attribute.
+    pub max_depth: usize,
+}
+
+impl Limits {
+    /// No limits.
+    pub const UNLIMITED: Self = Self {
+        max_bytes: usize::MAX,
+        max_items: usize::MAX,
+        max_depth: usize::MAX,
+    };
+
+    /// Runs `f` with these limits enforced, on the current thread with the `std` feature,
+    /// restoring the previous limits afterwards.
+    pub fn scope<T>(self, f: impl FnOnce() -> T) -> T {
+        struct Restore(Option<State>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                state::replace(self.0);
+                ACTIVE_SCOPES.store(
+                    ACTIVE_SCOPES.load(Ordering::Relaxed).saturating_sub(1),
+                    Ordering::Relaxed,
+                );
+            }
+        }
+
+        ACTIVE_SCOPES.store(ACTIVE_SCOPES.load(Ordering::Relaxed) + 1, Ordering::Relaxed);
+        let _restore = Restore(state::replace(Some(State {
+            remaining_bytes: self.max_bytes,
+            max_items: self.max_items,
+            max_depth: self.max_depth,
+            depth: 0,
+        })));
+        f()
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+/// A field of [`Limits`] that was exceeded, reported by [`Error::Limit`](crate::Error::Limit).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Limit {
+    /// [`Limits::max_bytes`]
+    MaxBytes,
+    /// [`Limits::max_items`]
+    MaxItems,
+    /// [`Limits::max_depth`]
+    MaxDepth,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MaxBytes => write!(f, "max_bytes"),
+            Self::MaxItems => write!(f, "max_items"),
+            Self::MaxDepth => write!(f, "max_depth"),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct State {
+    remaining_bytes: usize,
+    max_items: usize,
+    max_depth: usize,
+    depth: usize,
+}
+
+/// The number of scopes active on any thread, so that decoding outside of a scope skips the state.
+static ACTIVE_SCOPES: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "std")]
+mod state {
+    use super::{Cell, State};
+
+    std::thread_local! {
+        static STATE: Cell<Option<State>> = const { Cell::new(None) };
+    }
+
+    pub fn get() -> Option<State> {
+        STATE.get()
+    }
+
+    pub fn replace(state: Option<State>) -> Option<State> {
+        STATE.replace(state)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod state {
+    use super::{AtomicUsize, Ordering, State};
+
+    static SET: AtomicUsize = AtomicUsize::new(0);
+    static REMAINING_BYTES: AtomicUsize = AtomicUsize::new(0);
+    static MAX_ITEMS: AtomicUsize = AtomicUsize::new(0);
+    static MAX_DEPTH: AtomicUsize = AtomicUsize::new(0);
+    static DEPTH: AtomicUsize = AtomicUsize::new(0);
+
+    pub fn get() -> Option<State> {
+        (SET.load(Ordering::Relaxed) != 0).then(|| State {
+            remaining_bytes: REMAINING_BYTES.load(Ordering::Relaxed),
+            max_items: MAX_ITEMS.load(Ordering::Relaxed),
+            max_depth: MAX_DEPTH.load(Ordering::Relaxed),
+            depth: DEPTH.load(Ordering::Relaxed),
+        })
+    }
+
+    pub fn replace(state: Option<State>) -> Option<State> {
+        let previous = get();
+        SET.store(usize::from(state.is_some()), Ordering::Relaxed);
+        if let Some(state) = state {
+            REMAINING_BYTES.store(state.remaining_bytes, Ordering::Relaxed);
+            MAX_ITEMS.store(state.max_items, Ordering::Relaxed);
+            MAX_DEPTH.store(state.max_depth, Ordering::Relaxed);
+            DEPTH.store(state.depth, Ordering::Relaxed);
+        }
+        previous
+    }
+}
+
+/// Updates the state of the innermost scope with `f`, if any scope is active.
+fn update(f: impl FnOnce(&mut State) -> Result<()>) -> Result<()> {
+    if ACTIVE_SCOPES.load(Ordering::Relaxed) == 0 {
+        return Ok(());
+    }
+    let Some(mut current) = state::get() else {
+        return Ok(());
+    };
+    f(&mut current)?;
+    state::replace(Some(current));
+    Ok(())
+}
+
+/// Accounts for `additional` items of type `T` about to be added to a collection already holding
+/// `len` items, returning [`Error::Limit`] if this exceeds the active [`Limits`].
+pub fn reserve<T>(len: usize, additional: usize) -> Result<()> {
+    update(|current| {
+        if len.saturating_add(additional) > current.max_items {
+            return Err(Error::Limit(Limit::MaxItems));
+        }
+        current.remaining_bytes = additional
+            .checked_mul(size_of::<T>())
+            .and_then(|bytes| current.remaining_bytes.checked_sub(bytes))
+            .ok_or(Error::Limit(Limit::MaxBytes))?;
+        Ok(())
+    })
+}
+
+/// Increments the nesting depth until the returned guard is dropped, returning [`Error::Limit`]
+/// if this exceeds the active [`Limits`].
+pub fn enter() -> Result<DepthGuard> {
+    update(|current| {
+        if current.depth >= current.max_depth {
+            return Err(Error::Limit(Limit::MaxDepth));
+        }
+        current.depth += 1;
+        Ok(())
+    })?;
+    Ok(DepthGuard(()))
+}
+
+/// Decrements the nesting depth incremented by [`enter`] when dropped.
+#[must_use]
+pub struct DepthGuard(());
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        let _ = update(|current| {
+            current.depth = current.depth.saturating_sub(1);
+            Ok(())
+        });
+    }
+}
+
+/// Returns the capacity to preallocate for a collection of `count` items of type `T`, bounded so
+/// that an untrusted tag cannot cause a large allocation before any items are decoded.
+#[must_use]
+pub const fn capacity<T>(count: usize) -> usize {
+    let max = if size_of::<T>() == 0 {
+        MAX_PREALLOCATION
+    } else {
+        MAX_PREALLOCATION / size_of::<T>()
+    };
+    if count < max {
+        count
+    } else {
+        max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_bytes() {
+        let limits = Limits {
+            max_bytes: 6,
+            ..Limits::UNLIMITED
+        };
+        limits.scope(|| {
+            assert!(reserve::<u16>(0, 2).is_ok());
+            assert!(reserve::<u16>(2, 1).is_ok());
+            assert!(matches!(
+                reserve::<u16>(3, 1),
+                Err(Error::Limit(Limit::MaxBytes))
+            ));
+        });
+        assert!(reserve::<u16>(0, 1000).is_ok());
+    }
+
+    #[test]
+    fn max_depth() {
+        let limits = Limits {
+            max_depth: 2,
+            ..Limits::UNLIMITED
+        };
+        limits.scope(|| {
+            let outer = enter().unwrap();
+            let inner = enter().unwrap();
+            assert!(matches!(enter(), Err(Error::Limit(Limit::MaxDepth))));
+            drop(inner);
+            assert!(enter().is_ok());
+            drop(outer);
+        });
+    }
+
+    #[test]
+    fn capacity_bounded() {
+        assert_eq!(capacity::<u8>(16), 16);
+        assert_eq!(capacity::<u32>(usize::MAX), MAX_PREALLOCATION / 4);
+        assert_eq!(capacity::<()>(usize::MAX), MAX_PREALLOCATION);
+    }
+}
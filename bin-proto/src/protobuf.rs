@@ -0,0 +1,655 @@
+//! Protocol Buffers wire format
+//!
+//! Structs with the `#[bin_proto(format = protobuf)]` attribute are encoded as protobuf messages,
+//! with each field given a number by `#[bin_proto(field_number = <expr>)]`. Fields are encoded as
+//! a [`Key`] followed by their value, and decoded in any order until the end of the stream, with
+//! unknown fields skipped. Fields must implement [`Field`], which is implemented for scalars,
+//! [`Option`]s of [`Value`]s, which are only encoded when present, and [`Vec`]s of [`Value`]s,
+//! which are repeated fields encoded as packed when possible. Nested messages are wrapped in
+//! [`Message`].
+//!
+//! Since a message is decoded until the end of the stream, it can only be the last field of a
+//! struct unless it is delimited by a length. A [`Message`] can be given its length in bytes with
+//! the `tag` or `tag_type` attributes, using [`Message::encoded_len`] for the `tag_value`.
+//!
+//! | Protobuf type | Rust type |
+//! |-|-|
+//! | `int32`, `int64`, `uint32`, `uint64` | [`i32`], [`i64`], [`u32`], [`u64`] |
+//! | `sint32`, `sint64` | [`SInt32`], [`SInt64`] |
+//! | `fixed32`, `fixed64` | [`Fixed32`], [`Fixed64`] |
+//! | `sfixed32`, `sfixed64` | [`SFixed32`], [`SFixed64`] |
+//! | `bool`, `float`, `double` | [`bool`], [`f32`], [`f64`] |
+//! | `string`, `bytes` | [`String`], [`Vec<u8>`] |
+//! | message | [`Message`] |
+//!
+//! ```
+//! # use bin_proto::{BitCodec, BitDecode, BitEncode, protobuf::{Message, SInt32}};
+//! #[derive(Debug, Default, BitDecode, BitEncode, PartialEq)]
+//! #[bin_proto(format = protobuf)]
+//! struct Point {
+//!     #[bin_proto(field_number = 1)]
+//!     x: SInt32,
+//!     #[bin_proto(field_number = 2)]
+//!     label: String,
+//! }
+//!
+//! #[derive(Debug, BitDecode, BitEncode, PartialEq)]
+//! #[bin_proto(format = protobuf)]
+//! struct Path {
+//!     #[bin_proto(field_number = 1)]
+//!     points: Vec<Message<Point>>,
+//!     #[bin_proto(field_number = 2)]
+//!     weights: Vec<u32>,
+//! }
+//!
+//! let path = Path {
+//!     points: vec![Message(Point { x: SInt32(-1), label: "a".into() })],
+//!     weights: vec![1, 300],
+//! };
+//! let bytes = [
+//!     0x0a, 0x05, 0x08, 0x01, 0x12, 0x01, b'a', // points
+//!     0x12, 0x03, 0x01, 0xac, 0x02, // weights, packed
+//! ];
+//! assert_eq!(path.encode_bytes(bin_proto::BigEndian).unwrap(), bytes);
+//! assert_eq!(Path::decode_bytes(&bytes, bin_proto::BigEndian).unwrap().0, path);
+//!
+//! #[derive(Debug, BitDecode, BitEncode, PartialEq)]
+//! struct Frame {
+//!     #[bin_proto(tag_type = u8, tag_value = self.point.encoded_len()? as u8)]
+//!     point: Message<Point>,
+//!     checksum: u8,
+//! }
+//!
+//! let frame = Frame {
+//!     point: Message(Point { x: SInt32(-1), label: "a".into() }),
+//!     checksum: 0xff,
+//! };
+//! let bytes = [0x05, 0x08, 0x01, 0x12, 0x01, b'a', 0xff];
+//! assert_eq!(frame.encode_bytes(bin_proto::BigEndian).unwrap(), bytes);
+//! assert_eq!(Frame::decode_bytes(&bytes, bin_proto::BigEndian).unwrap().0, frame);
+//! ```
+
+use crate::{limits, BitDecode, BitEncode, Error, Result, Untagged};
+
+use alloc::{string::String, vec::Vec};
+use bitstream_io::{BitRead, BitReader, BitWrite, BitWriter, Endianness, LittleEndian};
+
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(not(feature = "std"))]
+use no_std_io2::io;
+
+/// The encoding of a field's value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WireType {
+    /// A varint.
+    Varint,
+    /// 8 little-endian bytes.
+    I64,
+    /// A varint length, followed by that many bytes.
+    Len,
+    /// 4 little-endian bytes.
+    I32,
+}
+
+/// The key preceding each field, consisting of its number and wire type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Key {
+    /// The number of the field.
+    pub field_number: u32,
+    /// The encoding of the field's value.
+    pub wire_type: WireType,
+}
+
+/// A type with a protobuf encoding, which can be an element of a repeated field.
+pub trait Value: Sized {
+    /// The wire type of the encoding.
+    const WIRE_TYPE: WireType;
+
+    /// Decodes a value.
+    fn decode_value<R: BitRead>(read: &mut R) -> Result<Self>;
+
+    /// Merges a decoded value into `self`. By default, the decoded value replaces `self`.
+    fn merge_value<R: BitRead>(&mut self, read: &mut R) -> Result<()> {
+        *self = Self::decode_value(read)?;
+        Ok(())
+    }
+
+    /// Encodes the value.
+    fn encode_value<W: BitWrite>(&self, write: &mut W) -> Result<()>;
+}
+
+/// A field of a protobuf message.
+pub trait Field {
+    /// Merges a value of the field, encoded with `wire_type`, into `self`.
+    fn merge_field<R: BitRead>(&mut self, read: &mut R, wire_type: WireType) -> Result<()>;
+
+    /// Encodes the field with the number `field_number`, omitting it if it has its default value.
+    fn encode_field<W: BitWrite>(&self, write: &mut W, field_number: u32) -> Result<()>;
+}
+
+/// `sint32`, encoded as a zigzag varint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SInt32(pub i32);
+
+/// `sint64`, encoded as a zigzag varint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SInt64(pub i64);
+
+/// `fixed32`, encoded as 4 little-endian bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed32(pub u32);
+
+/// `fixed64`, encoded as 8 little-endian bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed64(pub u64);
+
+/// `sfixed32`, encoded as 4 little-endian bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SFixed32(pub i32);
+
+/// `sfixed64`, encoded as 8 little-endian bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SFixed64(pub i64);
+
+/// A nested message, encoded as its length in bytes followed by the message.
+///
+/// `T` is usually a struct with the `#[bin_proto(format = protobuf)]` attribute. Singular message
+/// fields are `Option<Message<T>>`, and a message that occurs more than once is merged into the
+/// earlier one. Outside of protobuf messages, the tag is the length of the
+/// message in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Message<T>(pub T);
+
+impl<T: BitEncode<()>> Message<T> {
+    /// Returns the length of the encoded message in bytes, excluding its length prefix.
+    pub fn encoded_len(&self) -> Result<usize> {
+        Ok(encode_message(&self.0)?.len())
+    }
+}
+
+impl Key {
+    fn from_varint(key: u64) -> Result<Self> {
+        let wire_type = match key & 0x7 {
+            0 => WireType::Varint,
+            1 => WireType::I64,
+            2 => WireType::Len,
+            5 => WireType::I32,
+            _ => return Err(Error::Protobuf("unsupported wire type")),
+        };
+        Ok(Self {
+            field_number: u32::try_from(key >> 3)?,
+            wire_type,
+        })
+    }
+}
+
+impl<Ctx> BitDecode<Ctx> for Key {
+    fn decode<R, E>(read: &mut R, _: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        Self::from_varint(decode_varint(read)?)
+    }
+}
+
+impl<Ctx> BitEncode<Ctx> for Key {
+    fn encode<W, E>(&self, write: &mut W, _: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        let wire_type = match self.wire_type {
+            WireType::Varint => 0,
+            WireType::I64 => 1,
+            WireType::Len => 2,
+            WireType::I32 => 5,
+        };
+        encode_varint(write, u64::from(self.field_number) << 3 | wire_type)
+    }
+}
+
+/// Decodes a varint of up to 10 bytes.
+pub fn decode_varint<R: BitRead>(read: &mut R) -> Result<u64> {
+    let first = read.read_to::<u8>()?;
+    decode_varint_from(read, first)
+}
+
+fn decode_varint_from<R: BitRead>(read: &mut R, first: u8) -> Result<u64> {
+    let mut byte = first;
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        if shift > 0 {
+            byte = read.read_to::<u8>()?;
+        }
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(Error::Protobuf("varint too long"))
+}
+
+/// Encodes a varint, 7 bits at a time, starting with the least significant.
+pub fn encode_varint<W: BitWrite>(write: &mut W, mut value: u64) -> Result<()> {
+    while value >= 0x80 {
+        #[allow(clippy::cast_possible_truncation)]
+        write.write::<8, u8>(value as u8 | 0x80)?;
+        value >>= 7;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    write.write::<8, u8>(value as u8)?;
+    Ok(())
+}
+
+/// Decodes the key of the next field, returning `None` if the stream ends before the key.
+pub fn decode_key<R: BitRead>(read: &mut R) -> Result<Option<Key>> {
+    let first = match read.read_to::<u8>() {
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        first => first?,
+    };
+    Key::from_varint(decode_varint_from(read, first)?).map(Some)
+}
+
+/// Skips the value of an unknown field.
+pub fn skip_field<R: BitRead>(read: &mut R, wire_type: WireType) -> Result<()> {
+    match wire_type {
+        WireType::Varint => {
+            decode_varint(read)?;
+        }
+        WireType::I64 => read.skip(64)?,
+        WireType::Len => {
+            let mut len = decode_varint(read)?;
+            while len > 0 {
+                let bytes = len.min(u64::from(u32::MAX / 8));
+                read.skip(u32::try_from(bytes)? * 8)?;
+                len -= bytes;
+            }
+        }
+        WireType::I32 => read.skip(32)?,
+    }
+    Ok(())
+}
+
+fn decode_len<R: BitRead>(read: &mut R) -> Result<usize> {
+    Ok(usize::try_from(decode_varint(read)?)?)
+}
+
+fn encode_len<W: BitWrite>(write: &mut W, bytes: &[u8]) -> Result<()> {
+    encode_varint(write, u64::try_from(bytes.len())?)?;
+    write.write_bytes(bytes)?;
+    Ok(())
+}
+
+fn decode_message<T: BitDecode<()>>(bytes: &[u8]) -> Result<T> {
+    let mut reader = BitReader::<_, LittleEndian>::new(io::Cursor::new(bytes));
+    T::decode::<_, LittleEndian>(&mut reader, &mut (), ())
+}
+
+fn encode_message<T: BitEncode<()>>(message: &T) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    message.encode::<_, LittleEndian>(
+        &mut BitWriter::endian(&mut bytes, LittleEndian),
+        &mut (),
+        (),
+    )?;
+    Ok(bytes)
+}
+
+fn expect_wire_type<T: Value>(wire_type: WireType) -> Result<()> {
+    if wire_type == T::WIRE_TYPE {
+        Ok(())
+    } else {
+        Err(Error::Protobuf("mismatched wire type"))
+    }
+}
+
+macro_rules! impl_varint {
+    ($ty:ty, $value:ident => $to_wire:expr, $wire:ident => $from_wire:expr) => {
+        impl Value for $ty {
+            const WIRE_TYPE: WireType = WireType::Varint;
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            fn decode_value<R: BitRead>(read: &mut R) -> Result<Self> {
+                let $wire = decode_varint(read)?;
+                Ok($from_wire)
+            }
+
+            #[allow(clippy::cast_sign_loss)]
+            fn encode_value<W: BitWrite>(&self, write: &mut W) -> Result<()> {
+                let $value = *self;
+                encode_varint(write, $to_wire)
+            }
+        }
+    };
+}
+
+impl_varint!(u32, value => value.into(), wire => wire as u32);
+impl_varint!(u64, value => value, wire => wire);
+impl_varint!(i32, value => i64::from(value) as u64, wire => wire as i32);
+impl_varint!(i64, value => value as u64, wire => wire as i64);
+impl_varint!(bool, value => value.into(), wire => wire != 0);
+impl_varint!(
+    SInt32,
+    value => u64::from((value.0 << 1 ^ value.0 >> 31) as u32),
+    wire => Self((wire as u32 >> 1) as i32 ^ -((wire & 1) as i32))
+);
+impl_varint!(
+    SInt64,
+    value => (value.0 << 1 ^ value.0 >> 63) as u64,
+    wire => Self((wire >> 1) as i64 ^ -((wire & 1) as i64))
+);
+
+macro_rules! impl_fixed {
+    (
+        $ty:ty,
+        $wire_type:ident,
+        $repr:ty,
+        $value:ident => $to_wire:expr,
+        $wire:ident => $from_wire:expr
+    ) => {
+        impl Value for $ty {
+            const WIRE_TYPE: WireType = WireType::$wire_type;
+
+            fn decode_value<R: BitRead>(read: &mut R) -> Result<Self> {
+                let $wire = read.read_as_to::<LittleEndian, $repr>()?;
+                Ok($from_wire)
+            }
+
+            fn encode_value<W: BitWrite>(&self, write: &mut W) -> Result<()> {
+                let $value = *self;
+                write.write_as_from::<LittleEndian, $repr>($to_wire)?;
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_fixed!(Fixed32, I32, u32, value => value.0, wire => Self(wire));
+impl_fixed!(Fixed64, I64, u64, value => value.0, wire => Self(wire));
+impl_fixed!(SFixed32, I32, i32, value => value.0, wire => Self(wire));
+impl_fixed!(SFixed64, I64, i64, value => value.0, wire => Self(wire));
+impl_fixed!(f32, I32, u32, value => value.to_bits(), wire => Self::from_bits(wire));
+impl_fixed!(f64, I64, u64, value => value.to_bits(), wire => Self::from_bits(wire));
+
+impl Value for Vec<u8> {
+    const WIRE_TYPE: WireType = WireType::Len;
+
+    fn decode_value<R: BitRead>(read: &mut R) -> Result<Self> {
+        let len = decode_len(read)?;
+        limits::reserve::<u8>(0, len)?;
+        Ok(read.read_to_vec(len)?)
+    }
+
+    fn encode_value<W: BitWrite>(&self, write: &mut W) -> Result<()> {
+        encode_len(write, self)
+    }
+}
+
+impl Value for String {
+    const WIRE_TYPE: WireType = WireType::Len;
+
+    fn decode_value<R: BitRead>(read: &mut R) -> Result<Self> {
+        Ok(Self::from_utf8(Vec::decode_value(read)?)?)
+    }
+
+    fn encode_value<W: BitWrite>(&self, write: &mut W) -> Result<()> {
+        encode_len(write, self.as_bytes())
+    }
+}
+
+impl<T> Value for Message<T>
+where
+    T: BitDecode<()> + BitEncode<()>,
+{
+    const WIRE_TYPE: WireType = WireType::Len;
+
+    fn decode_value<R: BitRead>(read: &mut R) -> Result<Self> {
+        Ok(Self(decode_message(&Vec::decode_value(read)?)?))
+    }
+
+    /// Merges the decoded message into `self`, as if the encodings of both were concatenated.
+    fn merge_value<R: BitRead>(&mut self, read: &mut R) -> Result<()> {
+        let mut bytes = encode_message(&self.0)?;
+        bytes.extend(Vec::decode_value(read)?);
+        self.0 = decode_message(&bytes)?;
+        Ok(())
+    }
+
+    fn encode_value<W: BitWrite>(&self, write: &mut W) -> Result<()> {
+        encode_len(write, &encode_message(&self.0)?)
+    }
+}
+
+impl<Ctx, Tag, T> BitDecode<Ctx, crate::Tag<Tag>> for Message<T>
+where
+    Tag: TryInto<usize>,
+    T: BitDecode<()>,
+{
+    fn decode<R, E>(read: &mut R, _: &mut Ctx, tag: crate::Tag<Tag>) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let len = tag.0.try_into().map_err(|_| Error::TagConvert)?;
+        limits::reserve::<u8>(0, len)?;
+        Ok(Self(decode_message(&read.read_to_vec(len)?)?))
+    }
+}
+
+impl<Ctx, T: BitEncode<()>> BitEncode<Ctx, Untagged> for Message<T> {
+    fn encode<W, E>(&self, write: &mut W, _: &mut Ctx, _: Untagged) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        write.write_bytes(&encode_message(&self.0)?)?;
+        Ok(())
+    }
+}
+
+macro_rules! impl_singular_field {
+    ($($ty:ty),*) => {
+        $(
+            impl Field for $ty {
+                fn merge_field<R: BitRead>(
+                    &mut self,
+                    read: &mut R,
+                    wire_type: WireType,
+                ) -> Result<()> {
+                    expect_wire_type::<Self>(wire_type)?;
+                    *self = Self::decode_value(read)?;
+                    Ok(())
+                }
+
+                #[allow(clippy::float_cmp)]
+                fn encode_field<W: BitWrite>(
+                    &self,
+                    write: &mut W,
+                    field_number: u32,
+                ) -> Result<()> {
+                    if *self == Self::default() {
+                        return Ok(());
+                    }
+                    Key {
+                        field_number,
+                        wire_type: Self::WIRE_TYPE,
+                    }
+                    .encode::<_, LittleEndian>(write, &mut (), ())?;
+                    self.encode_value(write)
+                }
+            }
+        )*
+    };
+}
+
+impl_singular_field!(
+    u32,
+    u64,
+    i32,
+    i64,
+    bool,
+    SInt32,
+    SInt64,
+    Fixed32,
+    Fixed64,
+    SFixed32,
+    SFixed64,
+    f32,
+    f64,
+    Vec<u8>,
+    String
+);
+
+impl<T: Value> Field for Option<T> {
+    fn merge_field<R: BitRead>(&mut self, read: &mut R, wire_type: WireType) -> Result<()> {
+        expect_wire_type::<T>(wire_type)?;
+        if let Some(value) = self {
+            value.merge_value(read)
+        } else {
+            *self = Some(T::decode_value(read)?);
+            Ok(())
+        }
+    }
+
+    fn encode_field<W: BitWrite>(&self, write: &mut W, field_number: u32) -> Result<()> {
+        if let Some(value) = self {
+            Key {
+                field_number,
+                wire_type: T::WIRE_TYPE,
+            }
+            .encode::<_, LittleEndian>(write, &mut (), ())?;
+            value.encode_value(write)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Value> Field for Vec<T> {
+    fn merge_field<R: BitRead>(&mut self, read: &mut R, wire_type: WireType) -> Result<()> {
+        if wire_type == WireType::Len && T::WIRE_TYPE != WireType::Len {
+            let bytes = Vec::decode_value(read)?;
+            let mut reader = BitReader::<_, LittleEndian>::new(io::Cursor::new(bytes.as_slice()));
+            while reader.position_in_bits()? < u64::try_from(bytes.len())? * 8 {
+                self.push(T::decode_value(&mut reader)?);
+            }
+        } else {
+            expect_wire_type::<T>(wire_type)?;
+            self.push(T::decode_value(read)?);
+        }
+        Ok(())
+    }
+
+    fn encode_field<W: BitWrite>(&self, write: &mut W, field_number: u32) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        if T::WIRE_TYPE == WireType::Len {
+            for value in self {
+                Key {
+                    field_number,
+                    wire_type: WireType::Len,
+                }
+                .encode::<_, LittleEndian>(write, &mut (), ())?;
+                value.encode_value(write)?;
+            }
+        } else {
+            let mut bytes = Vec::new();
+            let mut writer = BitWriter::endian(&mut bytes, LittleEndian);
+            for value in self {
+                value.encode_value(&mut writer)?;
+            }
+            Key {
+                field_number,
+                wire_type: WireType::Len,
+            }
+            .encode::<_, LittleEndian>(write, &mut (), ())?;
+            encode_len(write, &bytes)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode<T: Value>(value: &T) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        value
+            .encode_value(&mut BitWriter::endian(&mut bytes, LittleEndian))
+            .unwrap();
+        bytes
+    }
+
+    fn decode<T: Value>(bytes: &[u8]) -> T {
+        T::decode_value(&mut BitReader::endian(bytes, LittleEndian)).unwrap()
+    }
+
+    #[test]
+    fn varint() {
+        assert_eq!(encode(&150u32), [0x96, 0x01]);
+        assert_eq!(decode::<u32>(&[0x96, 0x01]), 150);
+    }
+
+    #[test]
+    fn negative_int32() {
+        let bytes = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+        assert_eq!(encode(&-1i32), bytes);
+        assert_eq!(decode::<i32>(&bytes), -1);
+    }
+
+    #[test]
+    fn zigzag() {
+        assert_eq!(encode(&SInt32(-2)), [0x03]);
+        assert_eq!(decode::<SInt32>(&[0x03]), SInt32(-2));
+        assert_eq!(encode(&SInt64(i64::MIN)), encode(&u64::MAX));
+        assert_eq!(decode::<SInt64>(&encode(&u64::MAX)), SInt64(i64::MIN));
+    }
+
+    #[test]
+    fn fixed() {
+        assert_eq!(encode(&SFixed32(-2)), [0xfe, 0xff, 0xff, 0xff]);
+        assert_eq!(encode(&1.5f64), [0, 0, 0, 0, 0, 0, 0xf8, 0x3f]);
+        assert_eq!(
+            decode::<f64>(&[0, 0, 0, 0, 0, 0, 0xf8, 0x3f]).to_bits(),
+            1.5f64.to_bits()
+        );
+    }
+
+    #[test]
+    fn key() {
+        assert_eq!(
+            decode_key(&mut BitReader::endian([0x1a].as_slice(), LittleEndian)).unwrap(),
+            Some(Key {
+                field_number: 3,
+                wire_type: WireType::Len
+            })
+        );
+        assert_eq!(
+            decode_key(&mut BitReader::endian([].as_slice(), LittleEndian)).unwrap(),
+            None
+        );
+        assert!(matches!(
+            decode_key(&mut BitReader::endian([0x80].as_slice(), LittleEndian)),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn skip_len() {
+        let mut reader = BitReader::endian([0x02, 0xaa, 0xbb, 0x08].as_slice(), LittleEndian);
+        skip_field(&mut reader, WireType::Len).unwrap();
+        assert_eq!(decode::<u32>(&[reader.read_to::<u8>().unwrap()]), 8);
+    }
+
+    #[test]
+    fn varint_too_long() {
+        assert!(matches!(
+            u64::decode_value(&mut BitReader::endian([0xff; 11].as_slice(), LittleEndian)),
+            Err(Error::Protobuf(_))
+        ));
+    }
+}
@@ -0,0 +1,380 @@
+//! Strings in encodings other than UTF-8
+
+use crate::{limits, util, BitDecode, BitEncode, Error, Result, Untagged};
+
+use alloc::{string::String, vec::Vec};
+use bitstream_io::{BigEndian, BitRead, BitWrite, Endianness, LittleEndian};
+
+/// A string encoded as UTF-16 code units, in the endianness of the codec.
+///
+/// The tag is the number of code units.
+///
+/// ```
+/// # use bin_proto::{BitEncodeExt, text::Utf16String, Untagged};
+/// assert_eq!(
+///     Utf16String("hi".into())
+///         .encode_bytes_ctx(bin_proto::LittleEndian, &mut (), Untagged)
+///         .unwrap(),
+///     vec![b'h', 0, b'i', 0]
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Utf16String(pub String);
+
+/// A string encoded as big-endian UTF-16 code units, regardless of the endianness of the codec.
+///
+/// The tag is the number of code units.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Utf16BeString(pub String);
+
+/// A string encoded as little-endian UTF-16 code units, regardless of the endianness of the codec.
+///
+/// The tag is the number of code units.
+///
+/// ```
+/// # use bin_proto::{BitEncodeExt, text::Utf16LeString, Untagged};
+/// assert_eq!(
+///     Utf16LeString("hi".into())
+///         .encode_bytes_ctx(bin_proto::BigEndian, &mut (), Untagged)
+///         .unwrap(),
+///     vec![b'h', 0, b'i', 0]
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Utf16LeString(pub String);
+
+/// A string encoded as ISO 8859-1, with one byte per character.
+///
+/// The tag is the number of bytes. Encoding fails with [`Error::Unrepresentable`] if the string
+/// contains characters above `U+00FF`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Latin1String(pub String);
+
+/// An ASCII string occupying exactly `N` bytes, padded with trailing `PAD` bytes.
+///
+/// Trailing `PAD` bytes are stripped when decoding. Encoding fails with [`Error::Length`] if the
+/// string is longer than `N` bytes, and with [`Error::Unrepresentable`] if the string isn't ASCII
+/// or ends with `PAD`, which wouldn't be decoded. Decoding fails with [`Error::NonAscii`] if a
+/// byte isn't ASCII.
+///
+/// ```
+/// # use bin_proto::{BitCodec, text::FixedString};
+/// let (name, _) = FixedString::<8, b' '>::decode_bytes(b"eth0    ", bin_proto::BigEndian).unwrap();
+/// assert_eq!(name.0, "eth0");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedString<const N: usize, const PAD: u8 = 0>(pub String);
+
+macro_rules! impl_utf16_string {
+    ($ty:ident, $order:ty) => {
+        impl $ty {
+            fn from_units(units: &[u16]) -> Result<Self> {
+                Ok(Self(String::from_utf16(units)?))
+            }
+        }
+
+        impl<Tag, Ctx> BitDecode<Ctx, crate::Tag<Tag>> for $ty
+        where
+            Tag: TryInto<usize>,
+        {
+            fn decode<R, E>(read: &mut R, ctx: &mut Ctx, tag: crate::Tag<Tag>) -> Result<Self>
+            where
+                R: BitRead,
+                E: Endianness,
+            {
+                let item_count = tag.0.try_into().map_err(|_| Error::TagConvert)?;
+                limits::reserve::<u16>(0, item_count)?;
+                let mut units = Vec::with_capacity(limits::capacity::<u16>(item_count));
+                for _ in 0..item_count {
+                    units.push(u16::decode::<_, $order>(read, ctx, ())?);
+                }
+                Self::from_units(&units)
+            }
+        }
+
+        impl<Ctx> BitDecode<Ctx, Untagged> for $ty {
+            fn decode<R, E>(read: &mut R, ctx: &mut Ctx, _: Untagged) -> Result<Self>
+            where
+                R: BitRead,
+                E: Endianness,
+            {
+                let units: Vec<u16> = util::decode_items_to_eof::<_, $order, _, _>(read, ctx)
+                    .collect::<Result<_>>()?;
+                Self::from_units(&units)
+            }
+        }
+
+        impl<Ctx> BitEncode<Ctx, Untagged> for $ty {
+            fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, _: Untagged) -> Result<()>
+            where
+                W: BitWrite,
+                E: Endianness,
+            {
+                for unit in self.0.encode_utf16() {
+                    unit.encode::<_, $order>(write, ctx, ())?;
+                }
+                Ok(())
+            }
+        }
+
+        #[cfg(feature = "prepend-tags")]
+        impl<Ctx> BitEncode<Ctx> for $ty {
+            fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, (): ()) -> Result<()>
+            where
+                W: BitWrite,
+                E: Endianness,
+            {
+                self.0
+                    .encode_utf16()
+                    .count()
+                    .encode::<_, E>(write, ctx, ())?;
+                self.encode::<_, E>(write, ctx, Untagged)
+            }
+        }
+
+        #[cfg(feature = "prepend-tags")]
+        impl<Ctx> BitDecode<Ctx> for $ty {
+            fn decode<R, E>(read: &mut R, ctx: &mut Ctx, (): ()) -> Result<Self>
+            where
+                R: BitRead,
+                E: Endianness,
+            {
+                let tag = usize::decode::<_, E>(read, ctx, ())?;
+                Self::decode::<_, E>(read, ctx, crate::Tag(tag))
+            }
+        }
+    };
+}
+
+impl_utf16_string!(Utf16String, E);
+impl_utf16_string!(Utf16BeString, BigEndian);
+impl_utf16_string!(Utf16LeString, LittleEndian);
+
+impl Latin1String {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self(bytes.iter().copied().map(char::from).collect())
+    }
+}
+
+impl<Tag, Ctx> BitDecode<Ctx, crate::Tag<Tag>> for Latin1String
+where
+    Tag: TryInto<usize>,
+{
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, tag: crate::Tag<Tag>) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let item_count = tag.0.try_into().map_err(|_| Error::TagConvert)?;
+        limits::reserve::<u8>(0, item_count)?;
+        let mut bytes = Vec::with_capacity(limits::capacity::<u8>(item_count));
+        for _ in 0..item_count {
+            bytes.push(u8::decode::<_, E>(read, ctx, ())?);
+        }
+        Ok(Self::from_bytes(&bytes))
+    }
+}
+
+impl<Ctx> BitDecode<Ctx, Untagged> for Latin1String {
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, _: Untagged) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let bytes: Vec<u8> =
+            util::decode_items_to_eof::<_, E, _, _>(read, ctx).collect::<Result<_>>()?;
+        Ok(Self::from_bytes(&bytes))
+    }
+}
+
+impl<Ctx> BitEncode<Ctx, Untagged> for Latin1String {
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, _: Untagged) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        for c in self.0.chars() {
+            u8::try_from(c)
+                .map_err(|_| Error::Unrepresentable(c))?
+                .encode::<_, E>(write, ctx, ())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "prepend-tags")]
+impl<Ctx> BitEncode<Ctx> for Latin1String {
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        self.0.chars().count().encode::<_, E>(write, ctx, ())?;
+        self.encode::<_, E>(write, ctx, Untagged)
+    }
+}
+
+#[cfg(feature = "prepend-tags")]
+impl<Ctx> BitDecode<Ctx> for Latin1String {
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let tag = usize::decode::<_, E>(read, ctx, ())?;
+        Self::decode::<_, E>(read, ctx, crate::Tag(tag))
+    }
+}
+
+impl<Ctx, const N: usize, const PAD: u8> BitDecode<Ctx> for FixedString<N, PAD> {
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let mut bytes = <[u8; N]>::decode::<_, E>(read, ctx, ())?.to_vec();
+        let len = bytes.iter().rposition(|&b| b != PAD).map_or(0, |i| i + 1);
+        bytes.truncate(len);
+        if let Some(&byte) = bytes.iter().find(|byte| !byte.is_ascii()) {
+            return Err(Error::NonAscii(byte));
+        }
+        Ok(Self(String::from_utf8(bytes)?))
+    }
+}
+
+impl<Ctx, const N: usize, const PAD: u8> BitEncode<Ctx> for FixedString<N, PAD> {
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        if let Some(c) = self.0.chars().find(|c| !c.is_ascii()) {
+            return Err(Error::Unrepresentable(c));
+        }
+        let bytes = self.0.as_bytes();
+        if bytes.last() == Some(&PAD) {
+            return Err(Error::Unrepresentable(char::from(PAD)));
+        }
+        if bytes.len() > N {
+            return Err(Error::Length {
+                max: N,
+                actual: bytes.len(),
+            });
+        }
+        util::encode_items::<_, E, _, _>(bytes, write, ctx)?;
+        util::encode_items::<_, E, _, _>(&[PAD].repeat(N - bytes.len()), write, ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod utf16 {
+        use super::*;
+
+        test_untagged_and_codec!(
+            Utf16String| Untagged, crate::Tag(2);
+            Utf16String("hł".into()) => [0x00, 0x68, 0x01, 0x42]
+        );
+
+        #[test]
+        fn unpaired_surrogate() {
+            assert!(matches!(
+                Utf16String::decode::<_, bitstream_io::BigEndian>(
+                    &mut bitstream_io::BitReader::endian(
+                        [0xd8u8, 0x00].as_slice(),
+                        bitstream_io::BigEndian
+                    ),
+                    &mut (),
+                    Untagged,
+                ),
+                Err(Error::FromUtf16(_))
+            ));
+        }
+    }
+
+    mod utf16_be {
+        use super::*;
+
+        test_codec!(
+            Utf16BeString| Untagged, crate::Tag(2);
+            Utf16BeString("hł".into()) => [0x00, 0x68, 0x01, 0x42]
+        );
+    }
+
+    mod utf16_le {
+        use super::*;
+
+        test_codec!(
+            Utf16LeString| Untagged, crate::Tag(2);
+            Utf16LeString("hł".into()) => [0x68, 0x00, 0x42, 0x01]
+        );
+    }
+
+    mod latin1 {
+        use super::*;
+
+        test_untagged_and_codec!(
+            Latin1String| Untagged, crate::Tag(2);
+            Latin1String("aé".into()) => [0x61, 0xe9]
+        );
+
+        #[test]
+        fn unrepresentable() {
+            assert!(matches!(
+                Latin1String("ł".into()).encode::<_, bitstream_io::BigEndian>(
+                    &mut bitstream_io::BitWriter::endian(Vec::new(), bitstream_io::BigEndian),
+                    &mut (),
+                    Untagged,
+                ),
+                Err(Error::Unrepresentable('ł'))
+            ));
+        }
+    }
+
+    mod fixed {
+        use super::*;
+
+        test_codec!(FixedString<4, b' '>; FixedString("ab".into()) => [b'a', b'b', b' ', b' ']);
+
+        #[test]
+        fn non_ascii() {
+            assert!(matches!(
+                FixedString::<2>::decode::<_, bitstream_io::BigEndian>(
+                    &mut bitstream_io::BitReader::endian(
+                        [0xc5u8, 0x82].as_slice(),
+                        bitstream_io::BigEndian
+                    ),
+                    &mut (),
+                    (),
+                ),
+                Err(Error::NonAscii(0xc5))
+            ));
+        }
+
+        #[test]
+        fn trailing_pad() {
+            assert!(matches!(
+                FixedString::<4, b' '>("ab ".into()).encode::<_, bitstream_io::BigEndian>(
+                    &mut bitstream_io::BitWriter::endian(Vec::new(), bitstream_io::BigEndian),
+                    &mut (),
+                    (),
+                ),
+                Err(Error::Unrepresentable(' '))
+            ));
+        }
+
+        #[test]
+        fn too_long() {
+            assert!(matches!(
+                FixedString::<1>("ab".into()).encode::<_, bitstream_io::BigEndian>(
+                    &mut bitstream_io::BitWriter::endian(Vec::new(), bitstream_io::BigEndian),
+                    &mut (),
+                    (),
+                ),
+                Err(Error::Length { max: 1, actual: 2 })
+            ));
+        }
+    }
+}
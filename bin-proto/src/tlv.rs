@@ -0,0 +1,169 @@
+use core::marker::PhantomData;
+
+use alloc::vec::Vec;
+use bitstream_io::{BitRead, BitReader, BitWrite, BitWriter, Endianness};
+
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(not(feature = "std"))]
+use no_std_io2::io;
+
+use crate::{
+    limits, BitDecode, BitEncode, Discriminable, Error, KnownDiscriminant, Result, Tag, Untagged,
+};
+
+/// A type-length-value record, with a type of type `T`, followed by the length in bytes of the
+/// value, of type `L`, and the value.
+///
+/// The value is decoded from exactly the number of bytes given by the length, with the type as its
+/// tag, so `V` is usually a derived `enum` with `T` as its `discriminant_type`. Values whose type
+/// is unknown to `V`, according to [`KnownDiscriminant::is_decodable`], are preserved as raw
+/// bytes.
+/// Errors decoding values of known types are returned, and values that don't use all of their
+/// bytes return [`Error::Underrun`]. The length is computed when encoding.
+///
+/// The length counts only the bytes of the value, and every record has a length, so formats whose
+/// lengths include the type and length, or with records that have no length, such as TCP options,
+/// cannot be represented.
+///
+/// ```
+/// # use bin_proto::{BitDecode, BitEncode, BitDecodeExt, Tlv, Untagged};
+/// #[derive(Debug, BitDecode, BitEncode, PartialEq)]
+/// #[bin_proto(discriminant_type = u8)]
+/// enum Setting {
+///     #[bin_proto(discriminant = 1)]
+///     Timeout(u16),
+///     #[bin_proto(discriminant = 2)]
+///     Retries(u8),
+/// }
+///
+/// let settings = Vec::<Tlv<u8, u8, Setting>>::decode_bytes_ctx(
+///     &[1, 2, 0x01, 0x2c, 8, 2, 0xff, 0xff],
+///     bin_proto::BigEndian,
+///     &mut (),
+///     Untagged,
+/// )
+/// .unwrap()
+/// .0;
+/// assert_eq!(
+///     settings,
+///     vec![
+///         Tlv::Known(Setting::Timeout(300)),
+///         Tlv::unknown(8, vec![0xff, 0xff]),
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Tlv<T, L, V> {
+    /// A value whose type is known to `V`.
+    Known(V),
+    /// A value whose type is unknown to `V`.
+    Unknown(UnknownValue<T, L>),
+}
+
+/// The raw value of a [`Tlv`] whose type is unknown, with a length of type `L`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnknownValue<T, L> {
+    /// The type of the value.
+    pub typ: T,
+    /// The raw bytes of the value.
+    pub value: Vec<u8>,
+    length: PhantomData<L>,
+}
+
+impl<T, L> UnknownValue<T, L> {
+    /// Creates a raw value of an unknown type.
+    pub const fn new(typ: T, value: Vec<u8>) -> Self {
+        Self {
+            typ,
+            value,
+            length: PhantomData,
+        }
+    }
+}
+
+impl<T, L, V> Tlv<T, L, V> {
+    /// Creates a record of an unknown type.
+    pub const fn unknown(typ: T, value: Vec<u8>) -> Self {
+        Self::Unknown(UnknownValue::new(typ, value))
+    }
+}
+
+impl<Ctx, T, L, V> BitDecode<Ctx> for Tlv<T, L, V>
+where
+    T: BitDecode<Ctx> + Copy,
+    L: BitDecode<Ctx> + TryInto<usize>,
+    V: BitDecode<Ctx, Tag<T>> + KnownDiscriminant<Discriminant = T>,
+{
+    fn decode<R, E>(read: &mut R, ctx: &mut Ctx, (): ()) -> Result<Self>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        let typ = T::decode::<_, E>(read, ctx, ())?;
+        let len = L::decode::<_, E>(read, ctx, ())?
+            .try_into()
+            .map_err(|_| Error::TagConvert)?;
+        limits::reserve::<u8>(0, len)?;
+        let value = read.read_to_vec(len)?;
+        if !V::is_decodable(&typ) {
+            return Ok(Self::unknown(typ, value));
+        }
+
+        let mut value_reader = BitReader::<_, E>::new(io::Cursor::new(value.as_slice()));
+        let known = V::decode::<_, E>(&mut value_reader, ctx, Tag(typ))?;
+        let read_bits = value_reader.position_in_bits()?;
+        let available_bits = u64::try_from(len)? * 8;
+        if read_bits == available_bits {
+            Ok(Self::Known(known))
+        } else {
+            Err(Error::Underrun {
+                read_bits,
+                available_bits,
+            })
+        }
+    }
+}
+
+impl<Ctx, T, L, V> BitEncode<Ctx> for Tlv<T, L, V>
+where
+    T: BitEncode<Ctx>,
+    L: BitEncode<Ctx> + TryFrom<usize>,
+    V: BitEncode<Ctx, Untagged> + Discriminable<Discriminant = T>,
+{
+    fn encode<W, E>(&self, write: &mut W, ctx: &mut Ctx, (): ()) -> Result<()>
+    where
+        W: BitWrite,
+        E: Endianness,
+    {
+        match self {
+            Self::Known(known) => {
+                let typ = known.discriminant().ok_or(Error::EncodeSkipped)?;
+                let mut value = Vec::new();
+                let mut value_writer = BitWriter::<_, E>::new(&mut value);
+                known.encode::<_, E>(&mut value_writer, ctx, Untagged)?;
+                value_writer.byte_align()?;
+                encode_record::<_, E, _, _, L>(write, ctx, &typ, &value)
+            }
+            Self::Unknown(UnknownValue { typ, value, .. }) => {
+                encode_record::<_, E, _, _, L>(write, ctx, typ, value)
+            }
+        }
+    }
+}
+
+fn encode_record<W, E, Ctx, T, L>(write: &mut W, ctx: &mut Ctx, typ: &T, value: &[u8]) -> Result<()>
+where
+    W: BitWrite,
+    E: Endianness,
+    T: BitEncode<Ctx>,
+    L: BitEncode<Ctx> + TryFrom<usize>,
+{
+    typ.encode::<_, E>(write, ctx, ())?;
+    L::try_from(value.len())
+        .map_err(|_| Error::TagConvert)?
+        .encode::<_, E>(write, ctx, ())?;
+    write.write_bytes(value)?;
+    Ok(())
+}
@@ -1,8 +1,8 @@
 //! Helper functions for dealing with iterators
 
-use crate::{BitDecode, BitEncode, Error, Result};
+use crate::{limits, BitDecode, BitEncode, Error, Result};
 
-use bitstream_io::{BitRead, BitWrite, Endianness};
+use bitstream_io::{BitRead, BitReader, BitWrite, Endianness};
 use core::iter;
 #[cfg(feature = "std")]
 use std::io;
@@ -39,8 +39,247 @@ where
     E: Endianness,
     T: BitDecode<Ctx>,
 {
-    iter::from_fn(|| match T::decode::<_, E>(read, ctx, ()) {
+    let mut index = 0;
+    iter::from_fn(move || match T::decode::<_, E>(read, ctx, ()) {
         Err(Error::Io(e)) if e.kind() == io::ErrorKind::UnexpectedEof => None,
-        other => Some(other),
+        other => {
+            let item = limits::reserve::<T>(index, 1).and(other);
+            index += 1;
+            Some(item)
+        }
     })
 }
+
+/// [`BitDecode`]s items until EOF, passing each item the tag returned by `tag` for its index
+pub fn decode_tagged_items_to_eof<'a, R, E, Ctx, T, Tag, F>(
+    read: &'a mut R,
+    ctx: &'a mut Ctx,
+    mut tag: F,
+) -> impl Iterator<Item = Result<T>> + use<'a, R, E, Ctx, T, Tag, F>
+where
+    R: BitRead,
+    E: Endianness,
+    T: BitDecode<Ctx, Tag>,
+    F: FnMut(usize) -> Tag,
+{
+    let mut index = 0;
+    iter::from_fn(move || {
+        let item_tag = tag(index);
+        match T::decode::<_, E>(read, ctx, item_tag) {
+            Err(Error::Io(e)) if e.kind() == io::ErrorKind::UnexpectedEof => None,
+            other => {
+                let item = limits::reserve::<T>(index, 1).and(other);
+                index += 1;
+                Some(item)
+            }
+        }
+    })
+}
+
+/// [`BitEncode`]s an iterator of parcels to the stream, passing each item the tag returned by
+/// `tag` for its index.
+///
+/// Does not include a length prefix.
+pub fn encode_tagged_items<'a, W, E, Ctx, T, Tag, F>(
+    items: impl IntoIterator<Item = &'a T>,
+    write: &mut W,
+    ctx: &mut Ctx,
+    mut tag: F,
+) -> Result<()>
+where
+    W: BitWrite,
+    E: Endianness,
+    T: BitEncode<Ctx, Tag> + 'a,
+    F: FnMut(usize) -> Tag,
+{
+    for (index, item) in items.into_iter().enumerate() {
+        item.encode::<_, E>(write, ctx, tag(index))?;
+    }
+    Ok(())
+}
+
+/// [`BitDecode`]s items until the predicate returns `true` for a decoded item
+///
+/// The item for which the predicate returned `true` is yielded if `inclusive` is set.
+pub fn decode_items_until<'a, R, E, Ctx, T, F>(
+    read: &'a mut R,
+    ctx: &'a mut Ctx,
+    mut predicate: F,
+    inclusive: bool,
+) -> impl Iterator<Item = Result<T>> + use<'a, R, E, Ctx, T, F>
+where
+    R: BitRead,
+    E: Endianness,
+    T: BitDecode<Ctx>,
+    F: FnMut(&T) -> bool,
+{
+    let mut done = false;
+    let mut index = 0;
+    iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        let item = match T::decode::<_, E>(read, ctx, ()) {
+            Ok(item) if predicate(&item) => {
+                done = true;
+                if !inclusive {
+                    return None;
+                }
+                Ok(item)
+            }
+            other => other,
+        };
+        let item = limits::reserve::<T>(index, 1).and(item);
+        index += 1;
+        done |= item.is_err();
+        Some(item)
+    })
+}
+
+/// Reads `bits` bits of padding, returning [`Error::Padding`] at the first bit that doesn't match
+/// `pattern`.
+///
+/// `pattern` is repeated for the length of the padding, taking the bits of each byte in the bit
+/// order of `E`, which is the bit order of `read`. Padding a whole number of bytes thus repeats
+/// the bytes of `pattern`. An empty pattern is equivalent to `&[0]`.
+pub fn decode_pad<R, E>(read: &mut R, bits: u32, pattern: &[u8]) -> Result<()>
+where
+    R: BitRead + ?Sized,
+    E: Endianness,
+{
+    let lsb0 = lsb0::<E>();
+    for offset in 0..bits {
+        if read.read_bit()? != pad_bit(pattern, offset, lsb0) {
+            return Err(Error::Padding { offset });
+        }
+    }
+    Ok(())
+}
+
+/// Writes `bits` bits of padding, repeating `pattern` as in [`decode_pad`].
+pub fn encode_pad<W, E>(write: &mut W, bits: u32, pattern: &[u8]) -> Result<()>
+where
+    W: BitWrite + ?Sized,
+    E: Endianness,
+{
+    let lsb0 = lsb0::<E>();
+    for offset in 0..bits {
+        write.write_bit(pad_bit(pattern, offset, lsb0))?;
+    }
+    Ok(())
+}
+
+/// Reads padding up to the next byte boundary, returning [`Error::Padding`] at the first bit that
+/// doesn't match `pattern` as in [`decode_pad`].
+pub fn decode_align<R, E>(read: &mut R, pattern: &[u8]) -> Result<()>
+where
+    R: BitRead + ?Sized,
+    E: Endianness,
+{
+    let lsb0 = lsb0::<E>();
+    let mut offset = 0;
+    while !read.byte_aligned() {
+        if read.read_bit()? != pad_bit(pattern, offset, lsb0) {
+            return Err(Error::Padding { offset });
+        }
+        offset += 1;
+    }
+    Ok(())
+}
+
+/// Writes padding up to the next byte boundary, repeating `pattern` as in [`decode_pad`].
+pub fn encode_align<W, E>(write: &mut W, pattern: &[u8]) -> Result<()>
+where
+    W: BitWrite + ?Sized,
+    E: Endianness,
+{
+    let lsb0 = lsb0::<E>();
+    let mut offset = 0;
+    while !write.byte_aligned() {
+        write.write_bit(pad_bit(pattern, offset, lsb0))?;
+        offset += 1;
+    }
+    Ok(())
+}
+
+/// Whether `E` reads the bits of a byte starting with the least significant bit.
+fn lsb0<E: Endianness>() -> bool {
+    BitReader::<_, E>::new(&[1u8][..])
+        .read_bit()
+        .unwrap_or_default()
+}
+
+fn pad_bit(pattern: &[u8], offset: u32, lsb0: bool) -> bool {
+    let Ok(index) = usize::try_from(offset / 8) else {
+        return false;
+    };
+    let mask = if lsb0 {
+        1 << (offset % 8)
+    } else {
+        0x80 >> (offset % 8)
+    };
+    pattern
+        .get(index.checked_rem(pattern.len()).unwrap_or_default())
+        .is_some_and(|byte| byte & mask != 0)
+}
+
+/// Converts decoded bits into flags, returning [`Error::UnknownFlags`] if `strict` is set and any
+/// bits don't correspond to a defined flag.
+#[cfg(feature = "bitflags")]
+pub fn flags_from_bits<T>(bits: T::Bits, strict: bool) -> Result<T>
+where
+    T: bitflags::Flags,
+{
+    if strict {
+        T::from_bits(bits).ok_or(Error::UnknownFlags)
+    } else {
+        Ok(T::from_bits_retain(bits))
+    }
+}
+
+/// An [`io::Read`] adapter that reads whole bytes from a [`BitRead`]
+///
+/// Used to read from an existing stream with a different bit order, by wrapping the adapter in a
+/// [`BitReader`](bitstream_io::BitReader).
+pub struct ByteReader<'a, R: ?Sized>(pub &'a mut R);
+
+impl<R> io::Read for ByteReader<'_, R>
+where
+    R: BitRead + ?Sized,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(byte) = buf.first_mut() else {
+            return Ok(0);
+        };
+        match self.0.read::<8, u8>() {
+            Ok(value) => {
+                *byte = value;
+                Ok(1)
+            }
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(0),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// An [`io::Write`] adapter that writes whole bytes to a [`BitWrite`]
+///
+/// Used to write to an existing stream with a different bit order, by wrapping the adapter in a
+/// [`BitWriter`](bitstream_io::BitWriter).
+pub struct ByteWriter<'a, W: ?Sized>(pub &'a mut W);
+
+impl<W> io::Write for ByteWriter<'_, W>
+where
+    W: BitWrite + ?Sized,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for byte in buf {
+            self.0.write::<8, u8>(*byte)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bin_proto::{BitCodec, BitDecode, BitEncode, Error};
+use bitstream_io::BigEndian;
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(assert = min <= max)]
+struct Range {
+    #[bin_proto(assert = min != 0)]
+    min: u8,
+    max: u8,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Reserved(
+    #[bin_proto(bits = 4, assert = field_0 == 0)] u8,
+    #[bin_proto(bits = 4)] u8,
+);
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(discriminant_type = u8)]
+enum Message {
+    #[bin_proto(discriminant = 1)]
+    Ping {
+        #[bin_proto(assert = (1..=3).contains(&len))]
+        len: u8,
+        #[bin_proto(tag = len as usize)]
+        data: Vec<u8>,
+    },
+}
+
+#[test]
+fn valid_struct_decoded() {
+    assert_eq!(
+        Range::decode_bytes(&[1, 2], BigEndian).unwrap(),
+        (Range { min: 1, max: 2 }, 16)
+    );
+}
+
+#[test]
+fn invalid_field_rejected() {
+    assert!(matches!(
+        Range::decode_bytes(&[0, 2], BigEndian),
+        Err(Error::Validation { field: "min", .. })
+    ));
+}
+
+#[test]
+fn invalid_struct_rejected() {
+    assert!(matches!(
+        Range::decode_bytes(&[3, 2], BigEndian),
+        Err(Error::Validation {
+            field: "Range",
+            message: "min <= max"
+        })
+    ));
+}
+
+#[test]
+fn invalid_encode_not_rejected() {
+    assert_eq!(
+        Range { min: 3, max: 2 }.encode_bytes(BigEndian).unwrap(),
+        vec![3, 2]
+    );
+}
+
+#[test]
+fn unnamed_field_rejected() {
+    assert_eq!(
+        Reserved::decode_bytes(&[0x0F], BigEndian).unwrap(),
+        (Reserved(0, 15), 8)
+    );
+    assert!(matches!(
+        Reserved::decode_bytes(&[0x1F], BigEndian),
+        Err(Error::Validation { field: "0", .. })
+    ));
+}
+
+#[test]
+fn variant_field_rejected() {
+    assert_eq!(
+        Message::decode_bytes(&[1, 1, 7], BigEndian).unwrap(),
+        (
+            Message::Ping {
+                len: 1,
+                data: vec![7]
+            },
+            24
+        )
+    );
+    assert!(matches!(
+        Message::decode_bytes(&[1, 4, 1, 2, 3, 4], BigEndian),
+        Err(Error::Validation { field: "len", .. })
+    ));
+}
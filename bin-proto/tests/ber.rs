@@ -0,0 +1,104 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bin_proto::{
+    ber::{Boolean, Integer, ObjectIdentifier, OctetString, Sequence, Utf8String},
+    BitCodec, BitDecode, BitEncode, Error,
+};
+use bitstream_io::BigEndian;
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct AlgorithmIdentifier {
+    algorithm: ObjectIdentifier,
+    #[bin_proto(explicit = 0)]
+    critical: Boolean,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Entry {
+    #[bin_proto(implicit = 1)]
+    name: Utf8String,
+    algorithm: Sequence<AlgorithmIdentifier>,
+    #[bin_proto(implicit = 2)]
+    data: OctetString,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Frame {
+    kind: u8,
+    #[bin_proto(tag_type = u16, tag_value = self.entries.len() as u16)]
+    entries: Vec<Sequence<Entry>>,
+    checksum: u8,
+}
+
+fn frame() -> Frame {
+    Frame {
+        kind: 7,
+        entries: vec![Sequence(Entry {
+            name: Utf8String("a".into()),
+            algorithm: Sequence(AlgorithmIdentifier {
+                algorithm: ObjectIdentifier(vec![2, 5, 4, 3]),
+                critical: Boolean(true),
+            }),
+            data: OctetString(vec![0xde, 0xad]),
+        })],
+        checksum: 0xff,
+    }
+}
+
+#[test]
+fn codec_embedded() {
+    let bytes = [
+        7, 0, 1, // kind, entries length
+        0x30, 0x13, // SEQUENCE
+        0x81, 0x01, b'a', // [1] IMPLICIT UTF8String
+        0x30, 0x0a, // SEQUENCE
+        0x06, 0x03, 0x55, 0x04, 0x03, // OBJECT IDENTIFIER 2.5.4.3
+        0xa0, 0x03, 0x01, 0x01, 0xff, // [0] EXPLICIT BOOLEAN
+        0x82, 0x02, 0xde, 0xad, // [2] IMPLICIT OCTET STRING
+        0xff, // checksum
+    ];
+    assert_eq!(frame().encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        Frame::decode_bytes(&bytes, BigEndian).unwrap(),
+        (frame(), 25 * 8)
+    );
+}
+
+#[test]
+fn decode_indefinite() {
+    let bytes = [
+        7, 0, 1, // kind, entries length
+        0x30, 0x80, // SEQUENCE, indefinite length
+        0x81, 0x01, b'a', // [1] IMPLICIT UTF8String
+        0x30, 0x80, // SEQUENCE, indefinite length
+        0x06, 0x03, 0x55, 0x04, 0x03, // OBJECT IDENTIFIER 2.5.4.3
+        0xa0, 0x80, 0x01, 0x01, 0xff, 0x00, 0x00, // [0] EXPLICIT BOOLEAN, indefinite length
+        0x00, 0x00, // end of SEQUENCE
+        0x82, 0x02, 0xde, 0xad, // [2] IMPLICIT OCTET STRING
+        0x00, 0x00, // end of SEQUENCE
+        0xff, // checksum
+    ];
+    assert_eq!(Frame::decode_bytes(&bytes, BigEndian).unwrap().0, frame());
+}
+
+#[test]
+fn decode_wrong_tag() {
+    assert!(matches!(
+        Sequence::<Entry>::decode_bytes(&[0x30, 0x03, 0x82, 0x01, b'a'], BigEndian),
+        Err(Error::Discriminant)
+    ));
+}
+
+#[test]
+fn decode_trailing_contents() {
+    #[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+    struct Single(#[bin_proto(explicit = 0)] Integer);
+
+    assert!(matches!(
+        Single::decode_bytes(&[0xa0, 0x04, 0x02, 0x01, 0x05, 0x00], BigEndian),
+        Err(Error::Underrun {
+            read_bits: 24,
+            available_bits: 32
+        })
+    ));
+}
@@ -0,0 +1,104 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bin_proto::{BitCodec, BitDecode, BitEncode, Error};
+use bitstream_io::{BigEndian, LittleEndian};
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(bit_order = lsb0)]
+struct Lsb0 {
+    #[bin_proto(bits = 3)]
+    a: u8,
+    #[bin_proto(bits = 5)]
+    b: u8,
+    c: u16,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(bit_order = msb0)]
+struct Msb0 {
+    #[bin_proto(bits = 3)]
+    a: u8,
+    #[bin_proto(bits = 5)]
+    b: u8,
+    c: u16,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(bit_order = lsb0)]
+struct Partial {
+    #[bin_proto(bits = 4)]
+    a: u8,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Outer {
+    partial: Partial,
+    lsb0: Lsb0,
+    trailer: u8,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Unaligned {
+    #[bin_proto(bits = 4)]
+    a: u8,
+    partial: Partial,
+}
+
+#[test]
+fn lsb0_with_big_endian_integers() {
+    let value = Lsb0 {
+        a: 3,
+        b: 21,
+        c: 0x1234,
+    };
+    let bytes = [0b1010_1011, 0x12, 0x34];
+    assert_eq!(value.encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(Lsb0::decode_bytes(&bytes, BigEndian).unwrap(), (value, 24));
+}
+
+#[test]
+fn msb0_with_little_endian_integers() {
+    let value = Msb0 {
+        a: 3,
+        b: 21,
+        c: 0x1234,
+    };
+    let bytes = [0b0111_0101, 0x34, 0x12];
+    assert_eq!(value.encode_bytes(LittleEndian).unwrap(), bytes);
+    assert_eq!(
+        Msb0::decode_bytes(&bytes, LittleEndian).unwrap(),
+        (value, 24)
+    );
+}
+
+#[test]
+fn nested_bit_order_is_byte_aligned() {
+    let value = Outer {
+        partial: Partial { a: 0xA },
+        lsb0: Lsb0 {
+            a: 3,
+            b: 21,
+            c: 0x1234,
+        },
+        trailer: 0xFF,
+    };
+    let bytes = [0x0A, 0b1010_1011, 0x12, 0x34, 0xFF];
+    assert_eq!(value.encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(Outer::decode_bytes(&bytes, BigEndian).unwrap(), (value, 40));
+}
+
+#[test]
+fn unaligned() {
+    let value = Unaligned {
+        a: 1,
+        partial: Partial { a: 2 },
+    };
+    assert!(matches!(
+        value.encode_bytes(BigEndian),
+        Err(Error::Unaligned)
+    ));
+    assert!(matches!(
+        Unaligned::decode_bytes(&[0x12], BigEndian),
+        Err(Error::Unaligned)
+    ));
+}
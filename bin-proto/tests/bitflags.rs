@@ -0,0 +1,57 @@
+#![cfg(all(feature = "derive", feature = "alloc", feature = "bitflags"))]
+
+use bin_proto::{bitflags::bitflags, impl_bitflags, BitCodec, BitDecode, BitEncode, Error};
+use bitstream_io::BigEndian;
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct Flags: u8 {
+        const RESERVED = 0b100;
+        const DONT_FRAGMENT = 0b010;
+        const MORE_FRAGMENTS = 0b001;
+    }
+}
+
+impl_bitflags!(Flags);
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct Options: u8 {
+        const A = 0b0001;
+        const B = 0b0010;
+    }
+}
+
+impl_bitflags!(Options, strict);
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Header {
+    #[bin_proto(bits = 3)]
+    flags: Flags,
+    #[bin_proto(bits = 5)]
+    offset: u8,
+    options: Options,
+}
+
+#[test]
+fn codec_flags_in_bitfield() {
+    let header = Header {
+        flags: Flags::DONT_FRAGMENT | Flags::MORE_FRAGMENTS,
+        offset: 1,
+        options: Options::B,
+    };
+    let bytes = header.encode_bytes(BigEndian).unwrap();
+    assert_eq!(bytes, vec![0b0110_0001, 0b0000_0010]);
+    assert_eq!(
+        Header::decode_bytes(&bytes, BigEndian).unwrap(),
+        (header, 16)
+    );
+}
+
+#[test]
+fn strict_flags_rejected() {
+    assert!(matches!(
+        Header::decode_bytes(&[0, 0b0000_0100], BigEndian),
+        Err(Error::UnknownFlags)
+    ));
+}
@@ -102,6 +102,37 @@ struct CtxCheckBoundsWithGenerics<T: Boolean>(CtxCheck, PhantomData<T>);
 #[bin_proto(ctx_bounds(CtxTrait))]
 struct CtxCheckTraitWrapper(CtxCheck);
 
+#[derive(Debug)]
+struct OuterCtx {
+    inner: CtxStruct,
+}
+
+#[derive(Debug, BitDecode, BitEncode)]
+#[bin_proto(ctx = OuterCtx)]
+struct ProjectedCtx(#[bin_proto(ctx = &mut __ctx.inner)] CtxCheck);
+
+struct Factor(u8);
+
+#[derive(Debug, PartialEq, Eq)]
+struct Scaled(u8);
+
+impl BitDecode<Factor> for Scaled {
+    fn decode<R, E>(read: &mut R, ctx: &mut Factor, (): ()) -> Result<Self, bin_proto::Error>
+    where
+        R: BitRead,
+        E: Endianness,
+    {
+        Ok(Self(u8::decode::<_, E>(read, &mut (), ())? * ctx.0))
+    }
+}
+
+#[derive(Debug, BitDecode, PartialEq, Eq)]
+struct ConstructedCtx {
+    factor: u8,
+    #[bin_proto(ctx = &mut Factor(factor))]
+    value: Scaled,
+}
+
 #[test]
 fn decode_ctx_passed() {
     let mut ctx = CtxStruct(false);
@@ -151,3 +182,37 @@ fn encode_ctx_passed_recur_trait() {
         .unwrap();
     assert!(ctx.0);
 }
+
+#[test]
+fn decode_ctx_projected() {
+    let mut ctx = OuterCtx {
+        inner: CtxStruct(false),
+    };
+    ProjectedCtx::decode_bytes_ctx(&[], BigEndian, &mut ctx, ()).unwrap();
+    assert!(ctx.inner.0);
+}
+
+#[test]
+fn encode_ctx_projected() {
+    let mut ctx = OuterCtx {
+        inner: CtxStruct(false),
+    };
+    ProjectedCtx(CtxCheck)
+        .encode_bytes_ctx(BigEndian, &mut ctx, ())
+        .unwrap();
+    assert!(ctx.inner.0);
+}
+
+#[test]
+fn decode_ctx_constructed() {
+    assert_eq!(
+        ConstructedCtx::decode_bytes_ctx(&[3, 5], BigEndian, &mut (), ()).unwrap(),
+        (
+            ConstructedCtx {
+                factor: 3,
+                value: Scaled(15),
+            },
+            16
+        )
+    );
+}
@@ -0,0 +1,174 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bin_proto::{
+    dynamic::{Field, Length, Schema, Value, Variant},
+    BitCodec, BitDecode, BitEncode, Describe,
+};
+use bitstream_io::BigEndian;
+
+#[derive(Debug, BitDecode, BitEncode, Describe, PartialEq, Eq)]
+#[bin_proto(discriminant_type = u8)]
+#[bin_proto(bits = 4)]
+enum Command {
+    #[bin_proto(discriminant = 1)]
+    Move { x: i16, y: i16 },
+    #[bin_proto(discriminant = 2)]
+    Say(#[bin_proto(tag_type = u8, tag_value = field_0.len() as u8)] String),
+    #[bin_proto(discriminant = 3)]
+    Stop,
+}
+
+#[derive(Debug, BitDecode, BitEncode, Describe, PartialEq, Eq)]
+#[bin_proto(magic = b"PK")]
+#[bin_proto(pad_after = 8)]
+struct Packet {
+    #[bin_proto(bits = 4)]
+    version: u8,
+    command: Command,
+    #[bin_proto(pad_before = 8)]
+    count: u16,
+    #[bin_proto(tag = count as usize)]
+    data: Vec<u8>,
+    #[bin_proto(skip)]
+    cached: bool,
+    checksum: [i8; 2],
+}
+
+#[test]
+fn derived_schema() {
+    let bits = |width| Schema::Bits {
+        width,
+        signed: false,
+    };
+    assert_eq!(
+        Packet::schema(),
+        Schema::Struct(vec![
+            Field {
+                magic: b"PK".to_vec(),
+                ..Field::new("version", bits(4))
+            },
+            Field::new(
+                "command",
+                Schema::Enum {
+                    discriminant: Box::new(bits(4)),
+                    variants: vec![
+                        Variant {
+                            name: "Move".into(),
+                            discriminant: Value::UInt(1),
+                            fields: vec![
+                                Field::new("x", Schema::I16),
+                                Field::new("y", Schema::I16)
+                            ],
+                        },
+                        Variant {
+                            name: "Say".into(),
+                            discriminant: Value::UInt(2),
+                            fields: vec![Field::new(
+                                "0",
+                                Schema::String(Length::Prefix(Box::new(Schema::U8))),
+                            )],
+                        },
+                        Variant {
+                            name: "Stop".into(),
+                            discriminant: Value::UInt(3),
+                            fields: Vec::new(),
+                        },
+                    ],
+                },
+            ),
+            Field {
+                pad_before: 8,
+                ..Field::new("count", Schema::U16)
+            },
+            Field::new(
+                "data",
+                Schema::Array {
+                    item: Box::new(Schema::U8),
+                    length: Length::Field("count".into()),
+                }
+            ),
+            Field {
+                pad_after: 8,
+                ..Field::new(
+                    "checksum",
+                    Schema::Array {
+                        item: Box::new(Schema::I8),
+                        length: Length::Fixed(2),
+                    }
+                )
+            },
+        ])
+    );
+}
+
+#[test]
+fn schema_matches_derive() {
+    let packet = Packet {
+        version: 3,
+        command: Command::Say("hi".into()),
+        count: 2,
+        data: vec![0xaa, 0xbb],
+        cached: false,
+        checksum: [-1, 1],
+    };
+    let bytes = packet.encode_bytes(BigEndian).unwrap();
+    let value = Value::Struct(vec![
+        ("version".into(), Value::UInt(3)),
+        (
+            "command".into(),
+            Value::Variant {
+                name: "Say".into(),
+                fields: vec![("0".into(), Value::String("hi".into()))],
+            },
+        ),
+        ("count".into(), Value::UInt(2)),
+        (
+            "data".into(),
+            Value::Array(vec![Value::UInt(0xaa), Value::UInt(0xbb)]),
+        ),
+        (
+            "checksum".into(),
+            Value::Array(vec![Value::Int(-1), Value::Int(1)]),
+        ),
+    ]);
+    assert_eq!(
+        Packet::schema().decode_bytes(&bytes, BigEndian).unwrap(),
+        (value.clone(), u64::try_from(bytes.len()).unwrap() * 8)
+    );
+    assert_eq!(
+        Packet::schema().encode_bytes(&value, BigEndian).unwrap(),
+        bytes
+    );
+}
+
+#[derive(BitDecode, BitEncode, Describe)]
+#[bin_proto(magic = b"\x01\x02")]
+struct Header {
+    #[bin_proto(magic = b"\x03")]
+    length: u32,
+}
+
+#[test]
+fn unmerged_container_magic() {
+    assert_eq!(
+        Header::schema(),
+        Schema::Struct(vec![
+            Field {
+                magic: vec![0x01, 0x02],
+                ..Field::new("", Schema::Struct(Vec::new()))
+            },
+            Field {
+                magic: vec![0x03],
+                ..Field::new("length", Schema::U32)
+            },
+        ])
+    );
+    let value = Value::Struct(vec![
+        ("".into(), Value::Struct(Vec::new())),
+        ("length".into(), Value::UInt(4)),
+    ]);
+    assert_eq!(
+        Header::schema().encode_bytes(&value, BigEndian).unwrap(),
+        Header { length: 4 }.encode_bytes(BigEndian).unwrap()
+    );
+}
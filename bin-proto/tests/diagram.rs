@@ -0,0 +1,86 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bin_proto::{BitDecode, BitEncode, Describe};
+
+#[derive(BitDecode, BitEncode, Describe)]
+struct Ipv4 {
+    #[bin_proto(bits = 4)]
+    version: u8,
+    #[bin_proto(bits = 4)]
+    ihl: u8,
+    type_of_service: u8,
+    total_length: u16,
+    identification: u16,
+    #[bin_proto(bits = 3)]
+    flags: u8,
+    #[bin_proto(bits = 13)]
+    fragment_offset: u16,
+    time_to_live: u8,
+    protocol: u8,
+    header_checksum: u16,
+    source_address: [u8; 4],
+    destination_address: [u8; 4],
+    #[bin_proto(untagged)]
+    options: Vec<u8>,
+}
+
+#[test]
+fn ipv4() {
+    assert_eq!(
+        Ipv4::schema().diagram(),
+        r" 0                   1                   2                   3
+ 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|version|  ihl  |type_of_service|         total_length          |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|        identification         |flags|     fragment_offset     |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+| time_to_live  |   protocol    |        header_checksum        |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|                        source_address                         |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|                      destination_address                      |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/                            options                            /
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+"
+    );
+}
+
+#[derive(BitDecode, BitEncode, Describe)]
+#[bin_proto(discriminant_type = u8)]
+enum Body {
+    #[bin_proto(discriminant = 1)]
+    Ping,
+    #[bin_proto(discriminant = 2)]
+    Data(#[bin_proto(tag_type = u16, tag_value = field_0.len() as u16)] Vec<u8>),
+}
+
+#[derive(BitDecode, BitEncode, Describe)]
+#[bin_proto(magic = b"\x7fELF")]
+struct Message {
+    #[bin_proto(bits = 1)]
+    urgent: bool,
+    #[bin_proto(pad_before = 7, magic = b"\x00")]
+    sequence: u64,
+    body: Body,
+}
+
+#[test]
+fn magic_padding_and_variants() {
+    assert_eq!(
+        Message::schema().diagram(),
+        r" 0                   1                   2                   3
+ 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|                          0x7f454c46                           |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|u|   padding   |     0x00      |           sequence            |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|                                                               |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|                               |     body      /  body fields  /
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+"
+    );
+}
@@ -0,0 +1,142 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bin_proto::{
+    dynamic::{Field, Length, Schema, Value},
+    BitCodec, BitDecode, BitEncode, Describe,
+};
+use bitstream_io::BigEndian;
+
+#[derive(BitDecode, BitEncode, Describe)]
+#[bin_proto(discriminant_type = u8)]
+enum Command {
+    #[bin_proto(discriminant = 1)]
+    Move { x: i16, y: i16 },
+    #[bin_proto(discriminant = 2)]
+    Stop,
+}
+
+#[derive(BitDecode, BitEncode, Describe)]
+#[bin_proto(magic = b"PK")]
+struct Packet {
+    #[bin_proto(bits = 4)]
+    version: u8,
+    #[bin_proto(bits = 4)]
+    flags: u8,
+    command: Command,
+    #[bin_proto(tag_type = u8, tag_value = self.name.len() as u8)]
+    name: String,
+}
+
+fn bytes() -> Vec<u8> {
+    Packet {
+        version: 1,
+        flags: 2,
+        command: Command::Move { x: -1, y: 2 },
+        name: "ok".into(),
+    }
+    .encode_bytes(BigEndian)
+    .unwrap()
+}
+
+#[test]
+fn tree() {
+    let dissection = Packet::schema().dissect_bytes(&bytes(), BigEndian);
+    assert!(dissection.error.is_none());
+    assert_eq!(
+        dissection.tree(false).to_string(),
+        r#"     0.0    16  (magic): [50 4b]
+     2.0     4  version: 1 (0x1)
+     2.4     4  flags: 2 (0x2)
+     3.0    40  command: Move
+     3.0     8    (discriminant): 1 (0x1)
+     4.0    16    x: -1
+     6.0    16    y: 2
+     8.0    24  name: "ok"
+"#
+    );
+    assert_eq!(
+        dissection.tree(true).to_string().lines().nth(5),
+        Some("\x1b[35m     4.0    16    x: -1\x1b[0m")
+    );
+}
+
+#[test]
+fn hexdump() {
+    let dissection = Packet::schema().dissect_bytes(&bytes(), BigEndian);
+    assert_eq!(
+        dissection.hexdump(false).to_string(),
+        "00000000  50 4b 12 01 ff ff 00 02  02 6f 6b                 |PK.......ok|\n"
+    );
+    assert_eq!(
+        dissection.hexdump(true).to_string(),
+        "00000000  \x1b[31m50\x1b[0m \x1b[31m4b\x1b[0m \x1b[32m12\x1b[0m \x1b[34m01\x1b[0m \
+         \x1b[35mff\x1b[0m \x1b[35mff\x1b[0m \x1b[36m00\x1b[0m \x1b[36m02\x1b[0m  \
+         \x1b[31m02\x1b[0m \x1b[31m6f\x1b[0m \x1b[31m6b\x1b[0m                 |PK.......ok|\n"
+    );
+}
+
+#[test]
+fn truncated() {
+    let bytes = bytes();
+    let dissection = Packet::schema().dissect_bytes(&bytes[..6], BigEndian);
+    assert_eq!(dissection.fields[3].path, ["command"]);
+    assert_eq!(dissection.fields[3].value, None);
+    assert_eq!(dissection.fields[4].path, ["command", "(discriminant)"]);
+    assert_eq!(dissection.fields[5].value, Some(Value::Int(-1)));
+    assert_eq!(dissection.fields[6].path, ["command", "y"]);
+    assert_eq!(dissection.fields[6].value, None);
+    assert_eq!(
+        dissection.error.as_ref().map(|(position, _)| *position),
+        Some(48)
+    );
+}
+
+#[test]
+fn array_items() {
+    let schema = Schema::Struct(vec![Field::new(
+        "points",
+        Schema::Array {
+            item: Box::new(Schema::Struct(vec![
+                Field::new("x", Schema::U8),
+                Field::new("y", Schema::U8),
+            ])),
+            length: Length::Untagged,
+        },
+    )]);
+    let dissection = schema.dissect_bytes(&[1, 2, 3, 4, 5], BigEndian);
+    assert!(dissection.error.is_none());
+    assert_eq!(
+        dissection.tree(false).to_string(),
+        r"     0.0    40  points: [2 items]
+     0.0    16    0
+     0.0     8      x: 1 (0x1)
+     1.0     8      y: 2 (0x2)
+     2.0    16    1
+     2.0     8      x: 3 (0x3)
+     3.0     8      y: 4 (0x4)
+"
+    );
+}
+
+#[test]
+fn padding() {
+    let schema = Schema::Struct(vec![Field {
+        pad_before: 4,
+        ..Field::new(
+            "value",
+            Schema::Bits {
+                width: 4,
+                signed: false,
+            },
+        )
+    }]);
+    assert_eq!(
+        schema
+            .dissect_bytes(&[0x0f], BigEndian)
+            .tree(false)
+            .to_string(),
+        r"     0.0     4  (padding): [00]
+     0.4     4  value: 15 (0xf)
+"
+    );
+}
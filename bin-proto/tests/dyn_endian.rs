@@ -0,0 +1,105 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bin_proto::{BitCodec, BitDecode, BitEncode, DynEndian};
+use bitstream_io::BigEndian;
+
+#[derive(Debug, Clone, Copy, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(discriminant_type = u16)]
+enum ByteOrder {
+    #[bin_proto(discriminant = 0x4949)]
+    Little,
+    #[bin_proto(discriminant = 0x4d4d)]
+    Big,
+}
+
+impl From<ByteOrder> for DynEndian {
+    fn from(value: ByteOrder) -> Self {
+        match value {
+            ByteOrder::Little => Self::Little,
+            ByteOrder::Big => Self::Big,
+        }
+    }
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Header {
+    byte_order: ByteOrder,
+    #[bin_proto(endian_from = byte_order)]
+    version: u16,
+    #[bin_proto(endian_from = byte_order)]
+    #[bin_proto(tag_type = u16, tag_value = entries.len() as u16)]
+    entries: Vec<u32>,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Unnamed(
+    ByteOrder,
+    #[bin_proto(endian_from = field_0)] u32,
+    #[bin_proto(endian_from = DynEndian::Big)] u16,
+);
+
+#[test]
+fn codec_little_endian() {
+    let header = Header {
+        byte_order: ByteOrder::Little,
+        version: 42,
+        entries: vec![0x0102_0304],
+    };
+    let bytes = [0x49, 0x49, 42, 0, 1, 0, 4, 3, 2, 1];
+    assert_eq!(header.encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        Header::decode_bytes(&bytes, BigEndian).unwrap(),
+        (header, 80)
+    );
+}
+
+#[test]
+fn codec_big_endian() {
+    let header = Header {
+        byte_order: ByteOrder::Big,
+        version: 42,
+        entries: vec![0x0102_0304],
+    };
+    let bytes = [0x4d, 0x4d, 0, 42, 0, 1, 1, 2, 3, 4];
+    assert_eq!(header.encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        Header::decode_bytes(&bytes, BigEndian).unwrap(),
+        (header, 80)
+    );
+}
+
+#[test]
+fn codec_unnamed() {
+    let value = Unnamed(ByteOrder::Little, 1, 2);
+    let bytes = [0x49, 0x49, 1, 0, 0, 0, 0, 2];
+    assert_eq!(value.encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        Unnamed::decode_bytes(&bytes, BigEndian).unwrap(),
+        (value, 64)
+    );
+}
+
+/// A constant named like the generated endianness parameter, which user expressions can refer to.
+const __E: u16 = 1;
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Offset {
+    byte_order: ByteOrder,
+    #[bin_proto(endian_from = byte_order)]
+    #[bin_proto(wire_type = u16, map = |wire: u16| wire - __E, map_back = |value: &u16| value + __E)]
+    value: u16,
+}
+
+#[test]
+fn user_expressions_keep_identifiers() {
+    let offset = Offset {
+        byte_order: ByteOrder::Little,
+        value: 1,
+    };
+    let bytes = [0x49, 0x49, 2, 0];
+    assert_eq!(offset.encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        Offset::decode_bytes(&bytes, BigEndian).unwrap(),
+        (offset, 32)
+    );
+}
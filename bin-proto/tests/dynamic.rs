@@ -0,0 +1,115 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bin_proto::{
+    dynamic::{Field, Length, Schema, Value, Variant},
+    BitCodec, BitDecode, BitEncode,
+};
+use bitstream_io::BigEndian;
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(discriminant_type = u8)]
+#[bin_proto(bits = 4)]
+enum Command {
+    #[bin_proto(discriminant = 1)]
+    Move { x: i16, y: i16 },
+    #[bin_proto(discriminant = 2)]
+    Say(#[bin_proto(tag_type = u8, tag_value = field_0.len() as u8)] String),
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(magic = b"PK")]
+struct Packet {
+    #[bin_proto(bits = 4)]
+    version: u8,
+    command: Command,
+    #[bin_proto(pad_before = 8)]
+    count: u16,
+    #[bin_proto(tag = count as usize)]
+    data: Vec<u8>,
+}
+
+fn schema() -> Schema {
+    let bits = |width| Schema::Bits {
+        width,
+        signed: false,
+    };
+    Schema::Struct(vec![
+        Field {
+            magic: b"PK".to_vec(),
+            ..Field::new("version", bits(4))
+        },
+        Field::new(
+            "command",
+            Schema::Enum {
+                discriminant: Box::new(bits(4)),
+                variants: vec![
+                    Variant {
+                        name: "Move".into(),
+                        discriminant: Value::UInt(1),
+                        fields: vec![Field::new("x", Schema::I16), Field::new("y", Schema::I16)],
+                    },
+                    Variant {
+                        name: "Say".into(),
+                        discriminant: Value::UInt(2),
+                        fields: vec![Field::new(
+                            "0",
+                            Schema::String(Length::Prefix(Box::new(Schema::U8))),
+                        )],
+                    },
+                ],
+            },
+        ),
+        Field {
+            pad_before: 8,
+            ..Field::new("count", Schema::U16)
+        },
+        Field::new("data", Schema::Bytes(Length::Field("count".into()))),
+    ])
+}
+
+fn value(command: Value) -> Value {
+    Value::Struct(vec![
+        ("version".into(), Value::UInt(3)),
+        ("command".into(), command),
+        ("count".into(), Value::UInt(2)),
+        ("data".into(), Value::Bytes(vec![0xaa, 0xbb])),
+    ])
+}
+
+#[test]
+fn matches_derive_struct_variant() {
+    let packet = Packet {
+        version: 3,
+        command: Command::Move { x: -1, y: 2 },
+        count: 2,
+        data: vec![0xaa, 0xbb],
+    };
+    let bytes = packet.encode_bytes(BigEndian).unwrap();
+    let value = value(Value::Variant {
+        name: "Move".into(),
+        fields: vec![("x".into(), Value::Int(-1)), ("y".into(), Value::Int(2))],
+    });
+    assert_eq!(
+        schema().decode_bytes(&bytes, BigEndian).unwrap(),
+        (value.clone(), u64::try_from(bytes.len()).unwrap() * 8)
+    );
+    assert_eq!(schema().encode_bytes(&value, BigEndian).unwrap(), bytes);
+}
+
+#[test]
+fn matches_derive_tuple_variant() {
+    let packet = Packet {
+        version: 3,
+        command: Command::Say("hi".into()),
+        count: 2,
+        data: vec![0xaa, 0xbb],
+    };
+    let bytes = packet.encode_bytes(BigEndian).unwrap();
+    let value = value(Value::Variant {
+        name: "Say".into(),
+        fields: vec![("0".into(), Value::String("hi".into()))],
+    });
+    assert_eq!(schema().decode_bytes(&bytes, BigEndian).unwrap().0, value);
+    assert_eq!(schema().encode_bytes(&value, BigEndian).unwrap(), bytes);
+    assert_eq!(Packet::decode_bytes(&bytes, BigEndian).unwrap().0, packet);
+}
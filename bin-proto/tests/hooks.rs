@@ -0,0 +1,92 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bin_proto::{BitDecode, BitDecodeExt, BitEncode, BitEncodeExt};
+use bitstream_io::BigEndian;
+
+#[derive(Debug, Default)]
+struct Session {
+    version: u8,
+    messages: u32,
+    checksum: u8,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(ctx = Session)]
+#[bin_proto(after_decode = __ctx.checksum = a ^ b)]
+#[bin_proto(before_encode = __ctx.messages += 1)]
+struct Hello {
+    #[bin_proto(after_decode = __ctx.version = version)]
+    #[bin_proto(before_encode = __ctx.version = self.version)]
+    version: u8,
+    a: u8,
+    b: u8,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(ctx = Session)]
+#[bin_proto(discriminant_type = u8)]
+#[bin_proto(after_decode = __ctx.checksum = __value.version())]
+#[bin_proto(before_encode = __ctx.messages += 1)]
+enum Message {
+    #[bin_proto(discriminant = 1)]
+    Hello {
+        #[bin_proto(after_decode = __ctx.version = version)]
+        #[bin_proto(before_encode = __ctx.version = *version)]
+        version: u8,
+    },
+}
+
+impl Message {
+    const fn version(&self) -> u8 {
+        match self {
+            Self::Hello { version } => *version,
+        }
+    }
+}
+
+#[test]
+fn after_decode_updates_ctx() {
+    let mut ctx = Session::default();
+    let (decoded, _) = Hello::decode_bytes_ctx(&[2, 0x0F, 0xF0], BigEndian, &mut ctx, ()).unwrap();
+    assert_eq!(
+        decoded,
+        Hello {
+            version: 2,
+            a: 0x0F,
+            b: 0xF0
+        }
+    );
+    assert_eq!(ctx.version, 2);
+    assert_eq!(ctx.checksum, 0xFF);
+    assert_eq!(ctx.messages, 0);
+}
+
+#[test]
+fn before_encode_updates_ctx() {
+    let mut ctx = Session::default();
+    let bytes = Hello {
+        version: 3,
+        a: 1,
+        b: 2,
+    }
+    .encode_bytes_ctx(BigEndian, &mut ctx, ())
+    .unwrap();
+    assert_eq!(bytes, vec![3, 1, 2]);
+    assert_eq!(ctx.version, 3);
+    assert_eq!(ctx.messages, 1);
+    assert_eq!(ctx.checksum, 0);
+}
+
+#[test]
+fn variant_hooks_update_ctx() {
+    let mut ctx = Session::default();
+    Message::decode_bytes_ctx(&[1, 4], BigEndian, &mut ctx, ()).unwrap();
+    assert_eq!(ctx.version, 4);
+    assert_eq!(ctx.checksum, 4);
+
+    Message::Hello { version: 5 }
+        .encode_bytes_ctx(BigEndian, &mut ctx, ())
+        .unwrap();
+    assert_eq!(ctx.version, 5);
+    assert_eq!(ctx.messages, 1);
+}
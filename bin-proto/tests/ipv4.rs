@@ -1,6 +1,6 @@
 #![cfg(all(feature = "derive", feature = "alloc"))]
 
-use bin_proto::{BitCodec, BitDecode, BitEncode};
+use bin_proto::{BitCodec, BitDecode, BitEncode, Error};
 use bitstream_io::BigEndian;
 
 #[derive(Debug, Copy, Clone, BitDecode, BitEncode, PartialEq)]
@@ -23,7 +23,7 @@ struct Flags {
 #[derive(Debug, Copy, Clone, BitDecode, BitEncode, PartialEq)]
 struct IPv4 {
     version: Version,
-    #[bin_proto(bits = 4)]
+    #[bin_proto(bits = 4, assert = internet_header_length >= 5)]
     internet_header_length: u8,
     #[bin_proto(bits = 6)]
     differentiated_services_code_point: u8,
@@ -42,6 +42,7 @@ struct IPv4 {
 }
 
 #[test]
+#[allow(clippy::identity_op)]
 fn can_encode_decode_ipv4() {
     let raw = [
         0b0100_0000 // Version: 4
@@ -89,3 +90,17 @@ fn can_encode_decode_ipv4() {
     assert_eq!((parsed, 160), IPv4::decode_bytes(&raw, BigEndian).unwrap());
     assert_eq!(raw, parsed.encode_bytes(BigEndian).unwrap().as_slice())
 }
+
+#[test]
+fn rejects_short_ipv4_header() {
+    let mut raw = [0; 20];
+    raw[0] = 0b0100_0000 // Version: 4
+        | 0b0100; // Header Length: 4
+    assert!(matches!(
+        IPv4::decode_bytes(&raw, BigEndian),
+        Err(Error::Validation {
+            field: "internet_header_length",
+            ..
+        })
+    ));
+}
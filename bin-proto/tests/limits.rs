@@ -0,0 +1,170 @@
+#![cfg(all(feature = "derive", feature = "alloc", feature = "std"))]
+
+use bin_proto::{
+    ber::OctetString,
+    limits::{Limit, Limits},
+    BitCodec, BitDecode, BitEncode, Error, Tlv,
+};
+use bitstream_io::BigEndian;
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Packet {
+    len: u32,
+    #[bin_proto(tag = len)]
+    payload: Vec<u64>,
+    #[bin_proto(untagged)]
+    trailer: String,
+}
+
+#[test]
+fn huge_tag_does_not_preallocate() {
+    assert!(matches!(
+        Packet::decode_bytes(&[0xff, 0xff, 0xff, 0xff], BigEndian),
+        Err(Error::Io(_))
+    ));
+}
+
+#[test]
+fn max_items_exceeded() {
+    let limits = Limits {
+        max_items: 1,
+        ..Limits::UNLIMITED
+    };
+    let bytes = [0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2];
+    assert!(matches!(
+        limits.scope(|| Packet::decode_bytes(&bytes, BigEndian)),
+        Err(Error::Limit(Limit::MaxItems))
+    ));
+}
+
+#[test]
+fn max_bytes_exceeded_by_untagged() {
+    let limits = Limits {
+        max_bytes: 10,
+        ..Limits::UNLIMITED
+    };
+    let bytes = [0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, b'a', b'b', b'c'];
+    assert!(matches!(
+        limits.scope(|| Packet::decode_bytes(&bytes, BigEndian)),
+        Err(Error::Limit(Limit::MaxBytes))
+    ));
+    assert_eq!(
+        limits
+            .scope(|| Packet::decode_bytes(&bytes[..14], BigEndian))
+            .unwrap()
+            .0,
+        Packet {
+            len: 1,
+            payload: vec![1],
+            trailer: "ab".into(),
+        }
+    );
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(discriminant_type = u8)]
+enum Expr {
+    #[bin_proto(discriminant = 0)]
+    Literal(u8),
+    #[bin_proto(discriminant = 1)]
+    Neg(Box<Expr>),
+    #[bin_proto(discriminant = 2)]
+    Sum(#[bin_proto(tag_type = u8, tag_value = field_0.len() as u8)] Vec<Self>),
+}
+
+#[test]
+fn max_depth_exceeded() {
+    let limits = Limits {
+        max_depth: 4,
+        ..Limits::UNLIMITED
+    };
+    let shallow = [2, 1, 1, 0, 7];
+    assert_eq!(
+        limits
+            .scope(|| Expr::decode_bytes(&shallow, BigEndian))
+            .unwrap()
+            .0,
+        Expr::Sum(vec![Expr::Neg(Box::new(Expr::Literal(7)))])
+    );
+
+    let deep = [1, 1, 1, 1, 0, 7];
+    assert!(matches!(
+        limits.scope(|| Expr::decode_bytes(&deep, BigEndian)),
+        Err(Error::Limit(Limit::MaxDepth))
+    ));
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(discriminant_type = u8, recursive)]
+enum Ping {
+    #[bin_proto(discriminant = 0)]
+    Stop,
+    #[bin_proto(discriminant = 1)]
+    Next(Box<Pong>),
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(recursive)]
+struct Pong(Ping);
+
+#[test]
+fn max_depth_exceeded_by_mutual_recursion() {
+    let limits = Limits {
+        max_depth: 4,
+        ..Limits::UNLIMITED
+    };
+    assert_eq!(
+        limits
+            .scope(|| Ping::decode_bytes(&[1, 0], BigEndian))
+            .unwrap()
+            .0,
+        Ping::Next(Box::new(Pong(Ping::Stop)))
+    );
+    assert!(matches!(
+        limits.scope(|| Ping::decode_bytes(&[1, 1, 0], BigEndian)),
+        Err(Error::Limit(Limit::MaxDepth))
+    ));
+}
+
+#[test]
+fn max_bytes_exceeded_by_tlv_value() {
+    let limits = Limits {
+        max_bytes: 2,
+        ..Limits::UNLIMITED
+    };
+    assert!(matches!(
+        limits.scope(|| Tlv::<u8, u8, Ping>::decode_bytes(&[7, 3, 1, 2, 3], BigEndian)),
+        Err(Error::Limit(Limit::MaxBytes))
+    ));
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(format = protobuf)]
+struct Blob {
+    #[bin_proto(field_number = 1)]
+    data: Vec<u8>,
+}
+
+#[test]
+fn max_bytes_exceeded_by_protobuf_bytes() {
+    let limits = Limits {
+        max_bytes: 2,
+        ..Limits::UNLIMITED
+    };
+    assert!(matches!(
+        limits.scope(|| Blob::decode_bytes(&[0x0a, 3, 1, 2, 3], BigEndian)),
+        Err(Error::Limit(Limit::MaxBytes))
+    ));
+}
+
+#[test]
+fn max_bytes_exceeded_by_ber_contents() {
+    let limits = Limits {
+        max_bytes: 2,
+        ..Limits::UNLIMITED
+    };
+    assert!(matches!(
+        limits.scope(|| OctetString::decode_bytes(&[0x04, 3, 1, 2, 3], BigEndian)),
+        Err(Error::Limit(Limit::MaxBytes))
+    ));
+}
@@ -0,0 +1,157 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use core::time::Duration;
+
+use bin_proto::{BitCodec, BitDecode, BitEncode, Error};
+use bitstream_io::BigEndian;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Level {
+    Low,
+    High,
+}
+
+impl TryFrom<u8> for Level {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Low),
+            1 => Ok(Self::High),
+            _ => Err(Error::Other("invalid level")),
+        }
+    }
+}
+
+impl From<&Level> for u8 {
+    fn from(value: &Level) -> Self {
+        match value {
+            Level::Low => 0,
+            Level::High => 1,
+        }
+    }
+}
+
+mod yes_no {
+    use bin_proto::{
+        bitstream_io::{BitRead, BitWrite, Endianness},
+        BitDecode, BitEncode, Result,
+    };
+
+    pub fn decode<R: BitRead, E: Endianness>(
+        read: &mut R,
+        ctx: &mut impl Sized,
+        tag: (),
+    ) -> Result<bool> {
+        Ok(u8::decode::<_, E>(read, ctx, tag)? == b'Y')
+    }
+
+    pub fn encode<W: BitWrite, E: Endianness>(
+        value: &bool,
+        write: &mut W,
+        ctx: &mut impl Sized,
+        tag: (),
+    ) -> Result<()> {
+        if *value { b'Y' } else { b'N' }.encode::<_, E>(write, ctx, tag)
+    }
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Mapped {
+    #[bin_proto(
+        wire_type = u8,
+        map = |secs: u8| Duration::from_secs(secs.into()),
+        try_map_back = |duration: &Duration| u8::try_from(duration.as_secs()),
+    )]
+    timeout: Duration,
+    #[bin_proto(
+        wire_type = u8,
+        bits = 4,
+        try_map = Level::try_from,
+        map_back = u8::from
+    )]
+    level: Level,
+    #[bin_proto(bits = 4)]
+    reserved: u8,
+    #[bin_proto(with = yes_no)]
+    enabled: bool,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct TaggedMapped {
+    count: u8,
+    #[bin_proto(
+        wire_type = Vec<u8>,
+        tag = count as usize,
+        map = |bytes: Vec<u8>| bytes.into_iter().map(u32::from).collect(),
+        map_back = |values: &Vec<u32>| values.iter().map(|value| *value as u8).collect(),
+    )]
+    values: Vec<u32>,
+}
+
+#[test]
+fn decode_mapped() {
+    assert_eq!(
+        Mapped::decode_bytes(&[5, 0x12, b'Y'], BigEndian).unwrap(),
+        (
+            Mapped {
+                timeout: Duration::from_secs(5),
+                level: Level::High,
+                reserved: 2,
+                enabled: true,
+            },
+            24
+        )
+    );
+}
+
+#[test]
+fn encode_mapped() {
+    assert_eq!(
+        Mapped {
+            timeout: Duration::from_secs(5),
+            level: Level::Low,
+            reserved: 3,
+            enabled: false,
+        }
+        .encode_bytes(BigEndian)
+        .unwrap(),
+        vec![5, 0x03, b'N']
+    );
+}
+
+#[test]
+fn try_map_error_propagated() {
+    assert!(matches!(
+        Mapped::decode_bytes(&[5, 0x22, b'Y'], BigEndian),
+        Err(Error::Other("invalid level"))
+    ));
+}
+
+#[test]
+fn try_map_back_error_propagated() {
+    assert!(matches!(
+        Mapped {
+            timeout: Duration::from_secs(256),
+            level: Level::Low,
+            reserved: 0,
+            enabled: false,
+        }
+        .encode_bytes(BigEndian),
+        Err(Error::TryFromInt(_))
+    ));
+}
+
+#[test]
+fn tagged_wire_type() {
+    let value = TaggedMapped {
+        count: 2,
+        values: vec![1, 2],
+    };
+    let bytes = value.encode_bytes(BigEndian).unwrap();
+    assert_eq!(bytes, vec![2, 1, 2]);
+    assert_eq!(
+        TaggedMapped::decode_bytes(&bytes, BigEndian).unwrap(),
+        (value, 24)
+    );
+}
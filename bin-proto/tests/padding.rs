@@ -0,0 +1,161 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bin_proto::{BitCodec, BitDecode, BitEncode, Error};
+use bitstream_io::BigEndian;
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(pad_before = 8, pad_value = 0xa5, strict)]
+struct Filled {
+    #[bin_proto(pad_after = 4, pad_value = 0xff)]
+    #[bin_proto(bits = 4)]
+    a: u8,
+    b: u8,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(discriminant_type = u8, strict)]
+enum Reserved {
+    #[bin_proto(discriminant = 1)]
+    A(#[bin_proto(pad_before = 16)] u8),
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(pad_before = 8, pad_value = 0xff)]
+struct Lenient(u8);
+
+#[test]
+fn codec_filled() {
+    let value = Filled { a: 1, b: 2 };
+    let bytes = [0xa5, 0x1f, 0x02];
+    assert_eq!(value.encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        Filled::decode_bytes(&bytes, BigEndian).unwrap(),
+        (value, 24)
+    );
+}
+
+#[test]
+fn strict_rejects_pattern_mismatch() {
+    assert!(matches!(
+        Filled::decode_bytes(&[0xa5, 0x1d, 0x02], BigEndian),
+        Err(Error::Padding { offset: 2 })
+    ));
+    assert!(matches!(
+        Filled::decode_bytes(&[0xa4, 0x1f, 0x02], BigEndian),
+        Err(Error::Padding { offset: 7 })
+    ));
+}
+
+#[test]
+fn strict_inherited_by_variant_fields() {
+    assert_eq!(
+        Reserved::decode_bytes(&[1, 0, 0, 7], BigEndian).unwrap(),
+        (Reserved::A(7), 32)
+    );
+    assert!(matches!(
+        Reserved::decode_bytes(&[1, 0, 1, 7], BigEndian),
+        Err(Error::Padding { offset: 15 })
+    ));
+}
+
+#[test]
+fn lenient_ignores_padding() {
+    assert_eq!(
+        Lenient::decode_bytes(&[0, 3], BigEndian).unwrap(),
+        (Lenient(3), 16)
+    );
+    assert_eq!(Lenient(3).encode_bytes(BigEndian).unwrap(), vec![0xff, 3]);
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(pad_value = 0xff, strict)]
+struct Inherited {
+    #[bin_proto(pad_after = 8)]
+    a: u8,
+    #[bin_proto(pad_after = 8, pad_with = [0x55])]
+    b: u8,
+}
+
+#[test]
+fn field_padding_inherits_pattern() {
+    let value = Inherited { a: 1, b: 2 };
+    let bytes = [0x01, 0xff, 0x02, 0x55];
+    assert_eq!(value.encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        Inherited::decode_bytes(&bytes, BigEndian).unwrap(),
+        (value, 32)
+    );
+    assert!(matches!(
+        Inherited::decode_bytes(&[0x01, 0x00, 0x02, 0x55], BigEndian),
+        Err(Error::Padding { offset: 0 })
+    ));
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(pad_after = 20, pad_with = b"\xaa\x55", strict)]
+struct Alternating(u8);
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(bit_order = lsb0, pad_value = 0xff, strict)]
+struct Aligned {
+    #[bin_proto(bits = 3)]
+    a: u8,
+}
+
+#[test]
+fn codec_alternating() {
+    let bytes = [0x07, 0xaa, 0x55, 0xa0];
+    assert_eq!(Alternating(7).encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        Alternating::decode_bytes(&bytes, BigEndian).unwrap(),
+        (Alternating(7), 28)
+    );
+    assert!(matches!(
+        Alternating::decode_bytes(&[0x07, 0xaa, 0x54, 0xa0], BigEndian),
+        Err(Error::Padding { offset: 15 })
+    ));
+}
+
+#[test]
+fn codec_aligned() {
+    let bytes = [0b1111_1101];
+    assert_eq!(Aligned { a: 5 }.encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        Aligned::decode_bytes(&bytes, BigEndian).unwrap(),
+        (Aligned { a: 5 }, 8)
+    );
+    assert!(matches!(
+        Aligned::decode_bytes(&[0b1110_1101], BigEndian),
+        Err(Error::Padding { offset: 1 })
+    ));
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(bit_order = lsb0, pad_with = b"\x0f\x30", strict)]
+struct AlignedPattern {
+    a: u8,
+    #[bin_proto(pad_before = 16, pad_with = b"\x0f\x30")]
+    #[bin_proto(bits = 3)]
+    b: u8,
+}
+
+#[test]
+fn codec_aligned_pattern_lsb0() {
+    // Padding takes the bits of the pattern least significant first, so whole bytes of padding
+    // repeat the pattern.
+    let bytes = [0x05, 0x0f, 0x30, 0b0111_1111];
+    let value = AlignedPattern { a: 5, b: 7 };
+    assert_eq!(value.encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        AlignedPattern::decode_bytes(&bytes, BigEndian).unwrap(),
+        (value, 32)
+    );
+    assert!(matches!(
+        AlignedPattern::decode_bytes(&[0x05, 0x0e, 0x30, 0b0111_1111], BigEndian),
+        Err(Error::Padding { offset: 0 })
+    ));
+    assert!(matches!(
+        AlignedPattern::decode_bytes(&[0x05, 0x0f, 0x30, 0b0110_1111], BigEndian),
+        Err(Error::Padding { offset: 1 })
+    ));
+}
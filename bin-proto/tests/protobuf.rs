@@ -0,0 +1,161 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bin_proto::{
+    protobuf::{Fixed32, Message, SInt64},
+    BitCodec, BitDecode, BitEncode, Error,
+};
+use bitstream_io::BigEndian;
+
+#[derive(Debug, Default, BitDecode, BitEncode, PartialEq)]
+#[bin_proto(format = protobuf)]
+struct Inner {
+    #[bin_proto(field_number = 1)]
+    id: u32,
+}
+
+#[derive(Debug, Default, BitDecode, BitEncode, PartialEq)]
+#[bin_proto(format = protobuf)]
+struct Outer {
+    #[bin_proto(field_number = 1)]
+    name: String,
+    #[bin_proto(field_number = 2)]
+    offset: SInt64,
+    #[bin_proto(field_number = 3)]
+    inner: Option<Message<Inner>>,
+    #[bin_proto(field_number = 4)]
+    values: Vec<i32>,
+    #[bin_proto(field_number = 5)]
+    checksum: Fixed32,
+    #[bin_proto(field_number = 6)]
+    tags: Vec<String>,
+    #[bin_proto(skip)]
+    cached: bool,
+}
+
+#[derive(Debug, Default, BitDecode, BitEncode, PartialEq)]
+#[bin_proto(format = protobuf)]
+struct Pair {
+    #[bin_proto(field_number = 1)]
+    first: u32,
+    #[bin_proto(field_number = 2)]
+    second: u32,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq)]
+#[bin_proto(format = protobuf)]
+struct Holder {
+    #[bin_proto(field_number = 1)]
+    pair: Option<Message<Pair>>,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq)]
+struct Envelope {
+    version: u8,
+    #[bin_proto(tag_type = u16, tag_value = self.kind.len() as u16)]
+    kind: String,
+    payload: Outer,
+}
+
+fn outer() -> Outer {
+    Outer {
+        name: "x".into(),
+        offset: SInt64(-3),
+        inner: Some(Message(Inner { id: 150 })),
+        values: vec![1, 2],
+        checksum: Fixed32(0x0102_0304),
+        tags: vec!["a".into(), "b".into()],
+        cached: false,
+    }
+}
+
+const OUTER: [u8; 25] = [
+    0x0a, 0x01, b'x', // name
+    0x10, 0x05, // offset
+    0x1a, 0x03, 0x08, 0x96, 0x01, // inner
+    0x22, 0x02, 0x01, 0x02, // values, packed
+    0x2d, 0x04, 0x03, 0x02, 0x01, // checksum
+    0x32, 0x01, b'a', 0x32, 0x01, b'b', // tags
+];
+
+#[test]
+fn codec_message() {
+    let bytes = &OUTER;
+    assert_eq!(outer().encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        Outer::decode_bytes(bytes, BigEndian).unwrap(),
+        (outer(), 25 * 8)
+    );
+}
+
+#[test]
+fn encode_defaults_omitted() {
+    assert!(Outer::default().encode_bytes(BigEndian).unwrap().is_empty());
+}
+
+#[test]
+fn decode_any_order_unpacked_and_unknown() {
+    let bytes = [
+        0x32, 0x01, b'a', // tags
+        0x20, 0x01, // values, unpacked
+        0x38, 0xac, 0x02, // unknown varint field 7
+        0x41, 0, 0, 0, 0, 0, 0, 0, 0, // unknown i64 field 8
+        0x4a, 0x02, 0xff, 0xff, // unknown length-delimited field 9
+        0x55, 0, 0, 0, 0, // unknown i32 field 10
+        0x2d, 0x04, 0x03, 0x02, 0x01, // checksum
+        0x0a, 0x01, b'x', // name
+        0x22, 0x01, 0x02, // values, packed
+        0x1a, 0x03, 0x08, 0x96, 0x01, // inner
+        0x10, 0x05, // offset
+        0x32, 0x01, b'b', // tags
+    ];
+    assert_eq!(Outer::decode_bytes(&bytes, BigEndian).unwrap().0, outer());
+}
+
+#[test]
+fn decode_wire_type_mismatch() {
+    assert!(matches!(
+        Outer::decode_bytes(&[0x08, 0x01], BigEndian),
+        Err(Error::Protobuf(_))
+    ));
+}
+
+#[test]
+fn codec_envelope() {
+    let envelope = Envelope {
+        version: 1,
+        kind: "o".into(),
+        payload: outer(),
+    };
+    let mut bytes = vec![1, 0, 1, b'o'];
+    bytes.extend_from_slice(&OUTER);
+    assert_eq!(envelope.encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        Envelope::decode_bytes(&bytes, BigEndian).unwrap().0,
+        envelope
+    );
+}
+
+#[test]
+fn decode_repeated_message_merged() {
+    let bytes = [
+        0x0a, 0x04, 0x08, 0x01, 0x10, 0x02, // pair
+        0x0a, 0x02, 0x10, 0x03, // pair, merged
+    ];
+    assert_eq!(
+        Holder::decode_bytes(&bytes, BigEndian).unwrap().0,
+        Holder {
+            pair: Some(Message(Pair {
+                first: 1,
+                second: 3
+            }))
+        }
+    );
+}
+
+#[test]
+fn skip_unknown_field_longer_than_stream() {
+    assert!(matches!(
+        Outer::decode_bytes(&[0x4a, 0x80, 0x80, 0x80, 0x80, 0x20], BigEndian),
+        Err(Error::Io(_))
+    ));
+}
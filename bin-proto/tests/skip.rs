@@ -1,6 +1,6 @@
 #![cfg(all(feature = "derive", feature = "alloc"))]
 
-use bin_proto::{BitCodec, BitDecode, BitEncode, Error};
+use bin_proto::{BitCodec, BitDecode, BitDecodeExt, BitEncode, Error};
 use bitstream_io::BigEndian;
 
 #[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
@@ -24,6 +24,22 @@ struct Skip {
     b: u8,
 }
 
+#[derive(Debug, PartialEq, Eq)]
+struct NoDefault(u8);
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct SkipDefault {
+    total_length: u8,
+    #[bin_proto(skip, default = total_length / 4)]
+    payload_len_words: u8,
+    #[bin_proto(skip, default = NoDefault(total_length + 1))]
+    no_default: NoDefault,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(ctx = u8)]
+struct SkipDefaultCtx(#[bin_proto(skip_decode, default = *__ctx)] u8);
+
 #[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
 #[bin_proto(discriminant_type = u8)]
 enum SkipEncodeEnum {
@@ -88,6 +104,30 @@ fn skip_struct() {
     assert_eq!(len, 8);
 }
 
+#[test]
+fn skip_default_struct() {
+    let (decoded, len) = SkipDefault::decode_bytes(&[20], BigEndian).unwrap();
+
+    assert_eq!(
+        decoded,
+        SkipDefault {
+            total_length: 20,
+            payload_len_words: 5,
+            no_default: NoDefault(21),
+        }
+    );
+    assert_eq!(len, 8);
+    assert_eq!(decoded.encode_bytes(BigEndian).unwrap(), vec![20]);
+}
+
+#[test]
+fn skip_default_ctx() {
+    let (decoded, len) = SkipDefaultCtx::decode_bytes_ctx(&[], BigEndian, &mut 3, ()).unwrap();
+
+    assert_eq!(decoded, SkipDefaultCtx(3));
+    assert_eq!(len, 0);
+}
+
 #[test]
 fn skip_encode_enum() {
     let a = SkipEncodeEnum::A;
@@ -1,4 +1,5 @@
 #![cfg(all(feature = "derive", feature = "alloc"))]
+#![allow(clippy::disallowed_names)]
 
 use std::marker::PhantomData;
 
@@ -68,10 +69,10 @@ pub struct Magic {
 #[test]
 fn named_fields_are_correctly_written() {
     assert_eq!(
-        vec![3, '2' as u8, 1],
+        vec![3, b'2', 1],
         Foobar {
             a: 3,
-            b: '2' as u8,
+            b: b'2',
             c: 1,
         }
         .encode_bytes(BigEndian)
@@ -85,12 +86,12 @@ fn named_fields_are_correctly_decoded() {
         (
             Foobar {
                 a: 3,
-                b: '2' as u8,
+                b: b'2',
                 c: 1,
             },
             24
         ),
-        Foobar::decode_bytes(&[3, '2' as u8, 1], BigEndian).unwrap()
+        Foobar::decode_bytes(&[3, b'2', 1], BigEndian).unwrap()
     );
 }
 
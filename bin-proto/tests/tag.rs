@@ -1,4 +1,5 @@
 #![cfg(all(feature = "derive", feature = "alloc"))]
+#![allow(clippy::disallowed_names)]
 
 use bin_proto::{BitCodec, BitDecode, BitEncode};
 use bitstream_io::BigEndian;
@@ -56,6 +57,25 @@ pub struct PrependedBits {
     pub data: Vec<u32>,
 }
 
+#[derive(BitDecode, BitEncode, Debug, PartialEq, Eq)]
+pub struct ItemTagged {
+    pub count: u8,
+    #[bin_proto(tag = count as usize)]
+    pub lens: Vec<u8>,
+    #[bin_proto(tag = count as usize, item_tag = lens[__index] as usize)]
+    pub records: Vec<Vec<u8>>,
+}
+
+#[derive(BitDecode, BitEncode, Debug, PartialEq, Eq)]
+pub struct PrependedItemTagged {
+    #[bin_proto(
+        tag_type = u8,
+        tag_value = self.records.len() as u8,
+        item_tag = __index + 1
+    )]
+    pub records: Vec<Vec<u8>>,
+}
+
 #[test]
 fn can_decode_length_prefix_3_elements() {
     assert_eq!(
@@ -212,3 +232,40 @@ fn can_encode_prepended_length_prefix_bits() {
         vec![96, 0, 0, 0, 32, 0, 0, 0, 64, 0, 0, 0, 96],
     );
 }
+
+#[test]
+fn can_codec_item_tags() {
+    let value = ItemTagged {
+        count: 2,
+        lens: vec![1, 2],
+        records: vec![vec![1], vec![2, 3]],
+    };
+    let bytes = [
+        2, // count
+        1, 2, // lens
+        1, // record 0
+        2, 3, // record 1
+    ];
+    assert_eq!(value.encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        ItemTagged::decode_bytes(&bytes, BigEndian).unwrap(),
+        (value, 48)
+    );
+}
+
+#[test]
+fn can_codec_prepended_item_tags() {
+    let value = PrependedItemTagged {
+        records: vec![vec![1], vec![2, 3]],
+    };
+    let bytes = [
+        2, // length prefix
+        1, // record 0
+        2, 3, // record 1
+    ];
+    assert_eq!(value.encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        PrependedItemTagged::decode_bytes(&bytes, BigEndian).unwrap(),
+        (value, 32)
+    );
+}
@@ -0,0 +1,152 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bin_proto::{BitCodec, BitDecode, BitEncode};
+use bitstream_io::BigEndian;
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Label {
+    #[bin_proto(write_value = self.data.len() as u8)]
+    len: u8,
+    #[bin_proto(tag = len as usize)]
+    data: Vec<u8>,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Name {
+    #[bin_proto(terminator = Label { len: 0, data: Vec::new() })]
+    labels: Vec<Label>,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(discriminant_type = u8)]
+enum Opt {
+    #[bin_proto(discriminant = 0)]
+    End,
+    #[bin_proto(discriminant = 1)]
+    Nop,
+    #[bin_proto(discriminant = 2)]
+    Value(u8),
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Options {
+    #[bin_proto(until = |opt: &Opt| *opt == Opt::End)]
+    options: Vec<Opt>,
+    trailer: u8,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct CString {
+    #[bin_proto(terminator = 0, include_terminator)]
+    bytes: Vec<u8>,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Checksummed {
+    #[bin_proto(terminator = 0, include_terminator, write_terminator)]
+    data: Vec<u8>,
+    checksum: u8,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Unwritten {
+    #[bin_proto(terminator = 0, write_terminator = false)]
+    bytes: Vec<u8>,
+    end: u8,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Discarded {
+    #[bin_proto(until = |opt: &Opt| *opt == Opt::End, include_terminator = false)]
+    options: Vec<Opt>,
+}
+
+#[test]
+fn terminator_excluded_and_written() {
+    let name = Name {
+        labels: vec![
+            Label {
+                len: 2,
+                data: b"ab".to_vec(),
+            },
+            Label {
+                len: 1,
+                data: b"c".to_vec(),
+            },
+        ],
+    };
+    let bytes = name.encode_bytes(BigEndian).unwrap();
+    assert_eq!(bytes, vec![2, b'a', b'b', 1, b'c', 0]);
+    assert_eq!(Name::decode_bytes(&bytes, BigEndian).unwrap(), (name, 48));
+}
+
+#[test]
+fn terminator_missing() {
+    assert!(Name::decode_bytes(&[1, b'a'], BigEndian).is_err());
+}
+
+#[test]
+fn until_included() {
+    let options = Options {
+        options: vec![Opt::Nop, Opt::Value(5), Opt::End],
+        trailer: 0xFF,
+    };
+    let bytes = options.encode_bytes(BigEndian).unwrap();
+    assert_eq!(bytes, vec![1, 2, 5, 0, 0xFF]);
+    assert_eq!(
+        Options::decode_bytes(&bytes, BigEndian).unwrap(),
+        (options, 40)
+    );
+}
+
+#[test]
+fn terminator_included_and_written() {
+    let string = CString {
+        bytes: vec![b'h', b'i', 0],
+    };
+    let bytes = string.encode_bytes(BigEndian).unwrap();
+    assert_eq!(bytes, vec![b'h', b'i', 0]);
+    assert_eq!(
+        CString::decode_bytes(&bytes, BigEndian).unwrap(),
+        (string, 24)
+    );
+}
+
+#[test]
+fn terminator_excluded_and_not_written() {
+    let unwritten = Unwritten {
+        bytes: vec![1, 2],
+        end: 0,
+    };
+    let bytes = unwritten.encode_bytes(BigEndian).unwrap();
+    assert_eq!(bytes, vec![1, 2, 0]);
+    assert_eq!(
+        Unwritten::decode_bytes(&[1, 2, 0, 0], BigEndian).unwrap(),
+        (unwritten, 32)
+    );
+}
+
+#[test]
+fn until_excluded() {
+    let discarded = Discarded {
+        options: vec![Opt::Nop, Opt::Value(5)],
+    };
+    assert_eq!(
+        Discarded::decode_bytes(&[1, 2, 5, 0], BigEndian).unwrap(),
+        (discarded, 32)
+    );
+}
+
+#[test]
+fn terminator_included_and_explicitly_written() {
+    let value = Checksummed {
+        data: vec![1, 2, 0],
+        checksum: 3,
+    };
+    let bytes = value.encode_bytes(BigEndian).unwrap();
+    assert_eq!(bytes, vec![1, 2, 0, 3]);
+    assert_eq!(
+        Checksummed::decode_bytes(&bytes, BigEndian).unwrap(),
+        (value, 32)
+    );
+}
@@ -0,0 +1,87 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bin_proto::{BitCodec, BitDecode, BitDecodeExt, BitEncode, Error, Tlv};
+use bitstream_io::BigEndian;
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(discriminant_type = u8)]
+enum DhcpOption {
+    #[bin_proto(discriminant = 1)]
+    SubnetMask([u8; 4]),
+    #[bin_proto(discriminant = 12)]
+    HostName(#[bin_proto(untagged)] String),
+    #[bin_proto(discriminant = 53)]
+    MessageType(MessageType),
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+#[bin_proto(discriminant_type = u8)]
+enum MessageType {
+    #[bin_proto(discriminant = 1)]
+    Discover,
+    #[bin_proto(discriminant = 2)]
+    Offer,
+}
+
+#[derive(Debug, BitDecode, BitEncode, PartialEq, Eq)]
+struct Options {
+    #[bin_proto(untagged)]
+    options: Vec<Tlv<u8, u16, DhcpOption>>,
+}
+
+#[test]
+fn codec_known_and_unknown() {
+    let options = Options {
+        options: vec![
+            Tlv::Known(DhcpOption::SubnetMask([255, 255, 255, 0])),
+            Tlv::unknown(50, vec![192, 168, 1, 2]),
+            Tlv::Known(DhcpOption::HostName("pc".into())),
+        ],
+    };
+    let bytes = [
+        1, 0, 4, 255, 255, 255, 0, 50, 0, 4, 192, 168, 1, 2, 12, 0, 2, b'p', b'c',
+    ];
+    assert_eq!(options.encode_bytes(BigEndian).unwrap(), bytes);
+    assert_eq!(
+        Options::decode_bytes(&bytes, BigEndian).unwrap(),
+        (options, 19 * 8)
+    );
+}
+
+#[test]
+fn length_mismatch() {
+    assert!(matches!(
+        Options::decode_bytes(&[1, 0, 5, 255, 255, 255, 0, 0], BigEndian),
+        Err(Error::Underrun {
+            read_bits: 32,
+            available_bits: 40
+        })
+    ));
+}
+
+#[test]
+fn known_type_with_invalid_value() {
+    assert!(matches!(
+        Options::decode_bytes(&[53, 0, 1, 9], BigEndian),
+        Err(Error::Discriminant)
+    ));
+}
+
+#[derive(Debug, BitDecode, PartialEq, Eq)]
+#[bin_proto(discriminant_type = u8)]
+enum Received {
+    #[bin_proto(discriminant = 1)]
+    Ack(u8),
+}
+
+#[test]
+fn decode_only_value() {
+    assert_eq!(
+        Tlv::<u8, u8, Received>::decode_bytes_ctx(&[1, 1, 7], BigEndian, &mut (), ()).unwrap(),
+        (Tlv::Known(Received::Ack(7)), 24)
+    );
+    assert_eq!(
+        Tlv::<u8, u8, Received>::decode_bytes_ctx(&[2, 1, 7], BigEndian, &mut (), ()).unwrap(),
+        (Tlv::unknown(2, vec![7]), 24)
+    );
+}
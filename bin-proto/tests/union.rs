@@ -0,0 +1,72 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use bin_proto::{BitCodec, BitDecode, BitDecodeExt, BitEncode, BitEncodeExt, Error, Selector, Tag};
+use bitstream_io::BigEndian;
+
+#[derive(BitDecode, BitEncode)]
+#[bin_proto(discriminant_type = u8)]
+union Value {
+    #[bin_proto(discriminant = 0)]
+    int: u32,
+    #[bin_proto(discriminant = 1)]
+    bytes: [u8; 2],
+    #[bin_proto(discriminant = 2)]
+    #[bin_proto(skip_encode)]
+    byte: u8,
+}
+
+#[derive(BitDecode, BitEncode)]
+struct Tagged {
+    kind: u8,
+    #[bin_proto(tag = kind)]
+    #[bin_proto(encode_tag = unsafe { Selector::new(self.kind) })]
+    value: Value,
+}
+
+#[test]
+fn decode_union_field() {
+    let (value, len) = Value::decode_bytes_ctx(&[0, 0, 1, 2], BigEndian, &mut (), Tag(0)).unwrap();
+    assert_eq!(unsafe { value.int }, 0x0102);
+    assert_eq!(len, 32);
+
+    let (value, len) = Value::decode_bytes_ctx(&[1, 2], BigEndian, &mut (), Tag(1)).unwrap();
+    assert_eq!(unsafe { value.bytes }, [1, 2]);
+    assert_eq!(len, 16);
+}
+
+#[test]
+fn decode_union_unknown_discriminant() {
+    assert!(matches!(
+        Value::decode_bytes_ctx(&[0], BigEndian, &mut (), Tag(3)),
+        Err(Error::Discriminant)
+    ));
+}
+
+#[test]
+fn encode_union_field() {
+    let value = Value { bytes: [1, 2] };
+    assert_eq!(
+        value
+            .encode_bytes_ctx(BigEndian, &mut (), unsafe { Selector::new(1) })
+            .unwrap(),
+        vec![1, 2]
+    );
+}
+
+#[test]
+fn encode_union_skipped_field() {
+    let value = Value { byte: 1 };
+    assert!(matches!(
+        value.encode_bytes_ctx(BigEndian, &mut (), unsafe { Selector::new(2) }),
+        Err(Error::EncodeSkipped)
+    ));
+}
+
+#[test]
+fn codec_tagged_union() {
+    let (tagged, len) = Tagged::decode_bytes(&[1, 3, 4], BigEndian).unwrap();
+    assert_eq!(tagged.kind, 1);
+    assert_eq!(unsafe { tagged.value.bytes }, [3, 4]);
+    assert_eq!(len, 24);
+    assert_eq!(tagged.encode_bytes(BigEndian).unwrap(), vec![1, 3, 4]);
+}